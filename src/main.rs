@@ -1,19 +1,20 @@
 use std::{
-    collections::BTreeMap,
     sync::{Arc, OnceLock, atomic::AtomicBool},
     time::Duration,
 };
 
 use anyhow::Ok;
-use parking_lot::Mutex;
-use small_fixed_array::FixedArray;
+use parking_lot::{Mutex, RwLock};
 
 use poise::serenity_prelude as serenity;
 use serenity::small_fixed_array::FixedString;
 
 use tts_core::{
-    analytics, create_db_handler, database,
-    structs::{Data, PollyVoice, RegexCache, Result, TTSMode},
+    analytics,
+    audio_cache::AudioCache,
+    catalogues::VoiceCatalogues,
+    create_db_handler, database,
+    structs::{Data, RegexCache, Result},
 };
 use tts_events::EventHandler;
 use tts_tasks::Looper as _;
@@ -56,36 +57,35 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
 
     let http = Arc::new(http_builder.build());
 
+    println!("Connecting to tts-services");
+    let tts_services = tts_core::voice::connect_tts_services(&config.tts_services).await?;
+    for service in tts_services.iter().filter(|service| service.is_healthy()) {
+        println!("Connected to {service} with {}", *service.capabilities());
+    }
+
     println!("Performing big startup join");
-    let tts_service = || config.tts_services[0].url.clone();
+    let tts_service = tts_services
+        .iter()
+        .find(|service| service.is_healthy())
+        .expect("at least one tts-service should have connected");
     let (
-        ws_connections,
         webhooks,
         guilds_db,
         userinfo_db,
         user_voice_db,
         guild_voice_db,
         nickname_db,
-        gtts_voices,
-        espeak_voices,
-        gcloud_voices,
-        polly_voices,
-        translation_languages,
+        catalogues,
         shard_count,
         premium_user,
     ) = tokio::try_join!(
-        setup_ws_stream(&config.tts_services),
         get_webhooks(&http, config.webhooks),
         create_db_handler!(pool.clone(), "guilds", "guild_id"),
         create_db_handler!(pool.clone(), "userinfo", "user_id"),
         create_db_handler!(pool.clone(), "user_voice", "user_id", "mode"),
         create_db_handler!(pool.clone(), "guild_voice", "guild_id", "mode"),
         create_db_handler!(pool.clone(), "nicknames", "guild_id", "user_id"),
-        fetch_voices(&reqwest, tts_service(), TTSMode::gTTS),
-        fetch_voices(&reqwest, tts_service(), TTSMode::eSpeak),
-        fetch_voices(&reqwest, tts_service(), TTSMode::gCloud),
-        fetch_voices::<Vec<PollyVoice>>(&reqwest, tts_service(), TTSMode::Polly),
-        fetch_translation_languages(&reqwest, tts_service()),
+        VoiceCatalogues::fetch_or_load_snapshot(&reqwest, &pool, tts_service),
         async { Ok(http.get_bot_gateway().await?.shards) },
         async {
             let res = serenity::UserId::new(802632257658683442)
@@ -122,6 +122,7 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
         entitlement_cache: mini_moka::sync::Cache::builder()
             .time_to_live(Duration::from_hours(1))
             .build(),
+        audio_cache: AudioCache::new(),
         startup_message,
         premium_avatar_url: FixedString::from_string_trunc(premium_user.face()),
         system_info: Mutex::new(sysinfo::System::new()),
@@ -131,27 +132,10 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
         webhooks,
         pool,
 
-        service_weight_lookups: FixedArray::try_from(
-            config
-                .tts_services
-                .iter()
-                .enumerate()
-                .flat_map(|(index, service)| {
-                    (1..=service.weight.get()).map(move |_| index.try_into().unwrap())
-                })
-                .collect::<Box<[_]>>(),
-        )
-        .unwrap(),
-        tts_services: FixedArray::try_from(
-            config
-                .tts_services
-                .into_iter()
-                .map(|service| service.url)
-                .collect::<Box<[_]>>(),
-        )
-        .unwrap(),
-        ws_connections,
+        tts_services: RwLock::new(tts_services),
+        tts_services_reload_lock: tokio::sync::Mutex::new(()),
         voice_connections: Mutex::default(),
+        left_home_channels: Mutex::default(),
 
         config: config.main,
         premium_config: config.premium,
@@ -160,17 +144,10 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
         website_info: Mutex::new(config.website_info),
         bot_list_tokens: Mutex::new(config.bot_list_tokens),
         fully_started: AtomicBool::new(false),
+        ready_shards: Mutex::default(),
         update_startup_lock: tokio::sync::Mutex::new(()),
 
-        espeak_voices,
-        gtts_voices,
-        polly_voices: polly_voices
-            .into_iter()
-            .map(|v| (v.id.clone(), v))
-            .collect::<BTreeMap<_, _>>(),
-        gcloud_voices: prepare_gcloud_voices(gcloud_voices),
-
-        translation_languages,
+        catalogues: RwLock::new(Arc::new(catalogues)),
     });
 
     let framework_options = poise::FrameworkOptions {
@@ -219,6 +196,9 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
     });
 
     start_ws_health_checks(&data_clone);
+    #[cfg(unix)]
+    tokio::spawn(reload_on_hangup(Arc::clone(&data_clone)));
+
     client
         .start_shards(shard_count.get())
         .await
@@ -229,8 +209,7 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
 async fn wait_until_shutdown() {
     use tokio::signal::unix as signal;
 
-    let [mut s1, mut s2, mut s3] = [
-        signal::signal(signal::SignalKind::hangup()).unwrap(),
+    let [mut s1, mut s2] = [
         signal::signal(signal::SignalKind::interrupt()).unwrap(),
         signal::signal(signal::SignalKind::terminate()).unwrap(),
     ];
@@ -238,10 +217,21 @@ async fn wait_until_shutdown() {
     tokio::select!(
         v = s1.recv() => v.unwrap(),
         v = s2.recv() => v.unwrap(),
-        v = s3.recv() => v.unwrap(),
     );
 }
 
+/// Reloads the tts-services from `config.toml` whenever SIGHUP is recieved.
+#[cfg(unix)]
+async fn reload_on_hangup(data: Arc<Data>) {
+    use tokio::signal::unix as signal;
+
+    let mut hangup = signal::signal(signal::SignalKind::hangup()).unwrap();
+    while hangup.recv().await.is_some() {
+        tracing::warn!("Recieved SIGHUP, reloading tts-services");
+        reload_tts_services(&data).await;
+    }
+}
+
 #[cfg(windows)]
 async fn wait_until_shutdown() {
     let (mut s1, mut s2) = (
@@ -11,7 +11,7 @@ use poise::serenity_prelude::{
 
 use tts_core::{
     common::{push_permission_names, random_footer},
-    constants::RED,
+    constants::{MAX_MESSAGE_LENGTH, RED},
     database_models::GuildRow,
     opt_ext::OptionTryUnwrap as _,
     require_guild,
@@ -20,7 +20,10 @@ use tts_core::{
     voice,
 };
 
-use crate::{REQUIRED_VC_PERMISSIONS, TTS_PFP_DESC};
+use crate::{
+    REQUIRED_VC_PERMISSIONS, TTS_PFP_DESC,
+    other::{build_request, safe_content},
+};
 
 /// Returns Some(GuildRow) on correct channel, otherwise None.
 async fn channel_check(
@@ -116,6 +119,23 @@ fn gtts_disabled_embed<'a>(
     )
 }
 
+#[cold]
+fn stage_audience_embed(msg: poise::CreateReply<'_>, raised_hand: bool) -> poise::CreateReply<'_> {
+    let (title, footer) = if raised_hand {
+        (
+            "I have requested to speak in this Stage",
+            "A Stage moderator needs to accept my request before I can be heard.",
+        )
+    } else {
+        (
+            "I cannot request to speak in this Stage",
+            "Please ask a Stage moderator to invite me to speak, or give me the Request to Speak permission.",
+        )
+    };
+
+    msg.embed(create_warning_embed(title, footer))
+}
+
 #[cold]
 fn required_prefix_embed<'a>(
     title_place: &'a mut ArrayString<46>,
@@ -174,13 +194,18 @@ pub async fn join(ctx: Context<'_>) -> CommandResult {
         (current_user.id, current_user.face())
     };
 
-    let (author_vc_bot_perms, communication_disabled_until) = {
+    let (author_vc_bot_perms, communication_disabled_until, is_stage) = {
         let guild = require_guild!(ctx);
         let bot_member = guild.members.get(&bot_id).try_unwrap()?;
         let author_vc = guild.channels.get(&author_vc).try_unwrap()?;
 
         let bot_vc_perms = guild.user_permissions_in(author_vc, bot_member);
-        (bot_vc_perms, bot_member.communication_disabled_until)
+        let is_stage = author_vc.base.kind == serenity::ChannelType::Stage;
+        (
+            bot_vc_perms,
+            bot_member.communication_disabled_until,
+            is_stage,
+        )
     };
 
     if let Some(communication_disabled_until) = communication_disabled_until
@@ -211,12 +236,14 @@ pub async fn join(ctx: Context<'_>) -> CommandResult {
             bot_id,
         };
 
+        data.left_home_channels.lock().remove(&guild_id);
         match voice::start_connection(&data, voice_context).await {
             voice::StartConnectionResult::Started(_) => {}
-            voice::StartConnectionResult::AlreadyIn((interconnect, bot_channel_id, _)) => {
+            voice::StartConnectionResult::AlreadyIn(entry) => {
                 let bot_channel_id =
-                    serenity::ChannelId::new(bot_channel_id.load(Ordering::SeqCst));
-                handle_vc_mismatch(ctx, author_vc, interconnect, bot_id, bot_channel_id).await?;
+                    serenity::ChannelId::new(entry.channel_id.load(Ordering::SeqCst));
+                handle_vc_mismatch(ctx, author_vc, entry.interconnect, bot_id, bot_channel_id)
+                    .await?;
                 return Ok(());
             }
             voice::StartConnectionResult::TimedOut => {
@@ -252,6 +279,11 @@ pub async fn join(ctx: Context<'_>) -> CommandResult {
 
     let mut msg = poise::CreateReply::default().embed(embed);
 
+    // The voice task unsuppresses the bot itself if it has `MUTE_MEMBERS`.
+    if is_stage && !author_vc_bot_perms.mute_members() {
+        msg = stage_audience_embed(msg, author_vc_bot_perms.request_to_speak());
+    }
+
     // In-perfect premium check, but we don't need to be perfect
     if data.config.gtts_disabled.load(Ordering::Relaxed) && guild_row.premium_user.is_none() {
         msg = gtts_disabled_embed(msg, &data.config.main_server_invite);
@@ -290,19 +322,28 @@ pub async fn leave(ctx: Context<'_>) -> CommandResult {
         (guild.id, channel_id)
     };
 
-    if channel_check(&ctx, author_vc).await?.is_none() {
+    let Some(guild_row) = channel_check(&ctx, author_vc).await? else {
         return Ok(());
-    }
+    };
 
+    let data = ctx.data();
     let result = if let Some(author_vc) = author_vc {
-        voice::leave_vc(&ctx.data(), guild_id, Some(author_vc)).await
+        voice::leave_vc(&data, guild_id, Some(author_vc)).await
     } else {
         voice::LeaveVCResult::Mismatch
     };
 
     match result {
         voice::LeaveVCResult::Left => {
-            ctx.say("Left voice channel!").await?;
+            data.left_home_channels.lock().insert(guild_id);
+
+            let msg = if guild_row.home_channel.is_some() {
+                "Left voice channel! I will not return to my 24/7 home channel until I am told to `/join` or it is changed."
+            } else {
+                "Left voice channel!"
+            };
+
+            ctx.say(msg).await?;
         }
         voice::LeaveVCResult::Mismatch => {
             ctx.send_error("You need to be in the same voice channel as me to make me leave!")
@@ -350,6 +391,194 @@ pub async fn clear(ctx: Context<'_>) -> CommandResult {
     Ok(())
 }
 
-pub fn commands() -> [Command; 3] {
-    [join(), leave(), clear()]
+/// Makes TTS Bot follow a member between voice channels!
+#[poise::command(
+    category = "Main Commands",
+    guild_only,
+    prefix_command,
+    slash_command,
+    required_bot_permissions = "SEND_MESSAGES | EMBED_LINKS"
+)]
+pub async fn follow(
+    ctx: Context<'_>,
+    #[description = "The member to follow, leave blank to stop following"] member: Option<
+        serenity::User,
+    >,
+) -> CommandResult {
+    let author_vc = ctx.author_vc();
+    let Some(guild_row) = channel_check(&ctx, author_vc).await? else {
+        return Ok(());
+    };
+
+    let bot_id = ctx.cache().current_user().id;
+    let (bot_vc, target_vc, missing_permissions) = {
+        let guild = require_guild!(ctx);
+        let get_vc = |user_id| {
+            guild
+                .voice_states
+                .get(&user_id)
+                .and_then(|vs| vs.channel_id)
+        };
+
+        let target_vc = member.as_ref().and_then(|m| get_vc(m.id));
+        let missing_permissions = if let Some(target_vc) = target_vc
+            && let Some(target_vc) = guild.channels.get(&target_vc)
+        {
+            let bot_member = guild.members.get(&bot_id).try_unwrap()?;
+            REQUIRED_VC_PERMISSIONS - guild.user_permissions_in(target_vc, bot_member)
+        } else {
+            serenity::Permissions::empty()
+        };
+
+        (get_vc(bot_id), target_vc, missing_permissions)
+    };
+
+    if bot_vc.is_none() || bot_vc != author_vc {
+        let msg = "You need to be in the same voice channel as me to change who I follow!";
+        ctx.send_error(msg).await?;
+        return Ok(());
+    }
+
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+    let Some(member) = member else {
+        if voice::set_follow_target(&data, guild_id, None).is_ok() {
+            ctx.say("I am no longer following anyone.").await?;
+        } else {
+            ctx.say("**Error**: I am not in a voice channel!").await?;
+        }
+
+        return Ok(());
+    };
+
+    if member.bot() {
+        ctx.send_error("I cannot follow other bots!").await?;
+        return Ok(());
+    }
+
+    // The home channel supervisor would otherwise drag the bot back, or leave it in the wrong channel.
+    if guild_row.home_channel.is_some() && data.is_premium_simple(ctx.http(), guild_id).await? {
+        let msg = "I stay in my 24/7 home channel, so cannot follow anyone! Use `/set home_channel` with no options to disable it.";
+        ctx.send_error(msg).await?;
+        return Ok(());
+    }
+
+    let Some(target_vc) = target_vc else {
+        let msg = aformat!("{} is not in a voice channel!", &member.name);
+        ctx.send_error(msg.as_str()).await?;
+        return Ok(());
+    };
+
+    if !missing_permissions.is_empty() {
+        let mut msg = String::from(
+            "I do not have permission to TTS in their voice channel, please ask a server administrator to give me: ",
+        );
+        push_permission_names(&mut msg, missing_permissions);
+
+        ctx.send_error(msg).await?;
+        return Ok(());
+    }
+
+    if voice::set_follow_target(&data, guild_id, Some((member.id, target_vc))).is_ok() {
+        let msg = aformat!("I will now follow {} between voice channels!", &member.name);
+        ctx.say(msg.as_str()).await?;
+    } else {
+        ctx.say("**Error**: I am not in a voice channel!").await?;
+    }
+
+    Ok(())
+}
+
+/// Makes TTS Bot read an announcement before any other messages!
+#[poise::command(
+    category = "Main Commands",
+    guild_only,
+    prefix_command,
+    slash_command,
+    required_bot_permissions = "SEND_MESSAGES"
+)]
+pub async fn announce(
+    ctx: Context<'_>,
+    #[description = "Stop the message currently being read"] interrupt: Option<bool>,
+    #[description = "The announcement to read"]
+    #[rest]
+    text: String,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+    let author = ctx.author();
+
+    let guild_row = data.guilds_db.get(guild_id.into()).await?;
+    let has_announce_role = guild_row.announce_role.is_some_and(|announce_role| {
+        let member_roles = match ctx {
+            Context::Application(poise::ApplicationContext { interaction, .. }) => {
+                interaction.member.as_deref().map(|m| &*m.roles)
+            }
+            Context::Prefix(poise::PrefixContext { msg, .. }) => {
+                msg.member.as_deref().map(|m| &*m.roles)
+            }
+        };
+
+        member_roles.is_some_and(|roles| roles.contains(&announce_role))
+    });
+
+    if !has_announce_role && !ctx.author_permissions()?.manage_guild() {
+        let msg = "You need Manage Server, or the role set with `/set announce_role`, to make announcements!";
+        ctx.send_error(msg).await?;
+        return Ok(());
+    }
+
+    let (member, mentions) = match ctx {
+        Context::Application(poise::ApplicationContext { interaction, .. }) => {
+            (interaction.member.as_deref(), &[][..])
+        }
+        Context::Prefix(poise::PrefixContext { msg, .. }) => {
+            (msg.member.as_deref(), &*msg.mentions)
+        }
+    };
+
+    let content = {
+        let guild = require_guild!(ctx);
+        safe_content(&guild, &text, mentions, &[])
+    };
+
+    if content.text.len() >= MAX_MESSAGE_LENGTH {
+        ctx.send_error("That announcement is too long to read out!")
+            .await?;
+        return Ok(());
+    }
+
+    let is_premium = data.is_premium_simple(ctx.http(), guild_id).await?;
+    let member_nick = member.and_then(|member| member.nick.as_deref());
+    let request = build_request(ctx, &guild_row, is_premium, author, member_nick, content).await?;
+    let Some((author_name, mut request)) = request else {
+        return Ok(());
+    };
+
+    request.priority = true;
+    let queued = voice::QueuedTTS {
+        author_id: author.id,
+        author_name,
+        settings: guild_row.queue_settings(),
+        request,
+    };
+
+    let interrupt = interrupt.unwrap_or(false);
+    if voice::queue_priority(&data, guild_id, queued, interrupt).is_ok() {
+        let msg = if interrupt {
+            "Stopped the current message, your announcement will be read next!"
+        } else {
+            "Your announcement will be read after the current message!"
+        };
+
+        ctx.say(msg).await?;
+    } else {
+        ctx.say("**Error**: I am not in a voice channel!").await?;
+    }
+
+    Ok(())
+}
+
+pub fn commands() -> [Command; 5] {
+    [join(), leave(), clear(), follow(), announce()]
 }
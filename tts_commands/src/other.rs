@@ -1,23 +1,45 @@
-use aformat::{aformat, astr};
+use std::{
+    borrow::Cow,
+    sync::{Arc, atomic::AtomicU64},
+};
+
+use aformat::aformat;
 use anyhow::Error;
 use num_format::{Locale, ToFormattedString};
 
 use poise::{
-    CreateReply,
-    serenity_prelude::{self as serenity, Mentionable as _, builder::*},
+    ChoiceParameter as _, CreateReply,
+    serenity_prelude::{
+        self as serenity, Mentionable as _,
+        builder::*,
+        futures::{StreamExt as _, TryStreamExt as _, stream},
+        small_fixed_array::FixedString,
+    },
 };
 
 use aformat::ToArrayString;
 use tts_core::{
+    audio_cache::AudioCacheKey,
+    chunking,
     common::{build_invite_components, fetch_audio, prepare_url},
-    constants::OPTION_SEPERATORS,
-    opt_ext::OptionTryUnwrap,
+    constants::{MAX_MESSAGE_LENGTH, OPTION_SEPERATORS},
+    database_models::GuildRow,
+    espeak,
+    opt_ext::OptionTryUnwrap as _,
+    process_msg::{self, MessageContent, TTSMessageKind},
     require_guild,
-    structs::{ApplicationContext, Command, CommandResult, Context, IsPremium, TTSMode},
+    structs::{ApplicationContext, Command, CommandResult, Context, IsPremium, Result, TTSMode},
     traits::PoiseContextExt as _,
+    voice,
 };
 
-use crate::TTS_PFP_DESC;
+use crate::{
+    TTS_PFP_DESC,
+    settings::{
+        can_change_mode, check_speaking_rate, check_valid_voice, mode_autocomplete,
+        voice_autocomplete,
+    },
+};
 
 /// Shows how long TTS Bot has been online
 #[poise::command(
@@ -38,62 +60,144 @@ pub async fn uptime(ctx: Context<'_>) -> CommandResult {
     Ok(())
 }
 
-/// Generates TTS and sends it in the current text channel!
-#[poise::command(
-    category = "Extra Commands",
-    prefix_command,
-    slash_command,
-    required_bot_permissions = "SEND_MESSAGES | ATTACH_FILES"
-)]
-pub async fn tts(
-    ctx: Context<'_>,
-    #[description = "The text to TTS"]
-    #[rest]
-    message: String,
-) -> CommandResult {
-    let is_unnecessary_command_invoke = async {
-        if !matches!(ctx, poise::Context::Prefix(_)) {
-            return Ok(false);
+/// The longest audio `/tts` will generate, in seconds.
+const MAX_ATTACHMENT_LENGTH: u16 = 600;
+/// The longest text, in bytes, sent to tts-service in one request by `/tts`.
+const MAX_CHUNK_LENGTH: usize = 500;
+/// How many chunks of one `/tts` message are generated at once.
+const MAX_CONCURRENT_CHUNKS: usize = 4;
+
+const TOO_LONG_MSG: &str = "**Error**: That message is too long to generate TTS for!";
+
+#[derive(poise::ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    #[name = "MP3"]
+    Mp3,
+    #[name = "OGG/Opus"]
+    Opus,
+    #[name = "WAV"]
+    Wav,
+}
+
+impl AudioFormat {
+    /// The name sent to tts-service as `preferred_format`.
+    fn service_name(self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Opus => "opus",
+            Self::Wav => "wav",
+        }
+    }
+
+    fn file_extension(self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Opus => "ogg",
+            Self::Wav => "wav",
         }
+    }
+}
+
+/// Settings picked for a single `/tts`, used instead of those of the author.
+#[derive(Default)]
+struct TTSOverrides {
+    mode: Option<TTSMode>,
+    voice: Option<String>,
+    speaking_rate: Option<f32>,
+    format: Option<AudioFormat>,
+}
 
-        let (guild_id, author_voice_cid, bot_voice_cid) = {
-            if let Some(guild) = ctx.guild() {
-                (
-                    guild.id,
-                    guild
-                        .voice_states
-                        .get(&ctx.author().id)
-                        .and_then(|vc| vc.channel_id),
-                    guild
-                        .voice_states
-                        .get(&ctx.cache().current_user().id)
-                        .and_then(|vc| vc.channel_id),
-                )
-            } else {
-                return Ok(false);
+/// Generates TTS and sends it in the current text channel!
+pub fn tts() -> Command {
+    // Only takes the text, as any options would be parsed out of its first words.
+    #[poise::command(
+        prefix_command,
+        required_bot_permissions = "SEND_MESSAGES | ATTACH_FILES"
+    )]
+    async fn prefix_tts(ctx: Context<'_>, #[rest] message: String) -> CommandResult {
+        let is_unnecessary_command_invoke = async {
+            let (guild_id, author_voice_cid, bot_voice_cid) = {
+                if let Some(guild) = ctx.guild() {
+                    (
+                        guild.id,
+                        guild
+                            .voice_states
+                            .get(&ctx.author().id)
+                            .and_then(|vc| vc.channel_id),
+                        guild
+                            .voice_states
+                            .get(&ctx.cache().current_user().id)
+                            .and_then(|vc| vc.channel_id),
+                    )
+                } else {
+                    return Ok(false);
+                }
+            };
+
+            if author_voice_cid.is_some() && author_voice_cid == bot_voice_cid {
+                let setup_channel = ctx.data().guilds_db.get(guild_id.into()).await?.channel;
+                if setup_channel == Some(ctx.channel_id().expect_channel()) {
+                    return Ok(true);
+                }
             }
+
+            Ok::<_, Error>(false)
         };
 
-        if author_voice_cid.is_some() && author_voice_cid == bot_voice_cid {
-            let setup_channel = ctx.data().guilds_db.get(guild_id.into()).await?.channel;
-            if setup_channel == Some(ctx.channel_id().expect_channel()) {
-                return Ok(true);
-            }
+        if is_unnecessary_command_invoke.await? {
+            ctx.say("You don't need to include the `/tts` for messages to be said!")
+                .await?;
+            Ok(())
+        } else {
+            tts_(ctx, ctx.author(), &message, TTSOverrides::default()).await
         }
+    }
 
-        Ok::<_, Error>(false)
-    };
+    /// Generates TTS and sends it in the current text channel!
+    #[poise::command(
+        category = "Extra Commands",
+        slash_command,
+        required_bot_permissions = "SEND_MESSAGES | ATTACH_FILES"
+    )]
+    async fn slash_tts(
+        ctx: Context<'_>,
+        #[description = "The text to TTS"] message: String,
+        #[description = "The TTS Mode to use, instead of yours"]
+        #[autocomplete = "mode_autocomplete"]
+        mode: Option<TTSMode>,
+        #[description = "The voice to use, instead of yours"]
+        #[autocomplete = "voice_autocomplete"]
+        voice: Option<String>,
+        #[description = "The speed to speak at, instead of your speaking rate"]
+        #[min = 0]
+        speaking_rate: Option<f32>,
+        #[description = "The audio format to generate"] format: Option<AudioFormat>,
+    ) -> CommandResult {
+        let overrides = TTSOverrides {
+            mode,
+            voice,
+            speaking_rate,
+            format,
+        };
 
-    if is_unnecessary_command_invoke.await? {
-        ctx.say("You don't need to include the `/tts` for messages to be said!")
-            .await?;
-        Ok(())
-    } else {
-        tts_(ctx, ctx.author(), &message).await
+        tts_(ctx, ctx.author(), &message, overrides).await
+    }
+
+    Command {
+        prefix_action: prefix_tts().prefix_action,
+        name: Cow::Borrowed("tts"),
+        ..slash_tts()
     }
 }
 
-async fn tts_(ctx: Context<'_>, author: &serenity::User, message: &str) -> CommandResult {
+async fn tts_(
+    ctx: Context<'_>,
+    author: &serenity::User,
+    message: &str,
+    overrides: TTSOverrides,
+) -> CommandResult {
+    // Set if the author's mode could not be used, so their other settings have been dropped.
+    let mut fell_back_to_espeak = false;
     let attachment = {
         let data = ctx.data();
         let http = ctx.http();
@@ -103,12 +207,35 @@ async fn tts_(ctx: Context<'_>, author: &serenity::User, message: &str) -> Comma
             None
         };
 
-        let (voice, mode) = data
+        let (mut voice, mut mode) = data
             .parse_user_or_guild_with_premium(author.id, guild_info)
             .await?;
 
+        if let Some(chosen_mode) = overrides.mode
+            && chosen_mode != mode
+        {
+            let guild_is_premium = guild_info.is_some_and(|(_, is_premium)| is_premium);
+            if !can_change_mode(&ctx, Some(chosen_mode), guild_is_premium).await? {
+                return Ok(());
+            }
+
+            let guild_id = guild_info.map(|(guild_id, _)| guild_id);
+            voice = data.resolve_voice(author.id, guild_id, chosen_mode).await?;
+            mode = chosen_mode;
+        }
+
+        if let Some(chosen_voice) = overrides.voice {
+            let chosen_voice_code = FixedString::from_str_trunc(&chosen_voice);
+            if !check_valid_voice(&data.catalogues(), &chosen_voice_code, mode) {
+                ctx.say("Invalid voice, do `/voices`").await?;
+                return Ok(());
+            }
+
+            voice = Cow::Owned(chosen_voice);
+        }
+
         let guild_row;
-        let translation_lang = if let Some((guild_id, is_premium)) = guild_info {
+        let mut translation_lang = if let Some((guild_id, is_premium)) = guild_info {
             guild_row = data.guilds_db.get(guild_id.into()).await?;
             guild_row.target_lang(IsPremium::from(is_premium))
         } else {
@@ -120,41 +247,145 @@ async fn tts_(ctx: Context<'_>, author: &serenity::User, message: &str) -> Comma
             .chars()
             .filter(|char| char.is_alphanumeric())
             .collect();
-        let speaking_rate = data.speaking_rate(author.id, mode).await?;
 
-        let tts_service_index = match ctx.guild_id() {
-            Some(guild_id) => data.select_tts_index(guild_id),
-            None => 0,
+        let use_local_espeak = data.config.force_local_espeak || !data.has_healthy_tts_service();
+        if use_local_espeak {
+            if overrides
+                .format
+                .is_some_and(|format| format != AudioFormat::Wav)
+            {
+                let msg =
+                    "**Error**: Only WAV audio can be generated right now, please try again later!";
+                ctx.say(msg).await?;
+                return Ok(());
+            }
+
+            // Local eSpeak cannot translate, so the audio must not be cached as translated.
+            translation_lang = None;
+            if mode != TTSMode::eSpeak {
+                mode = TTSMode::eSpeak;
+                voice = Cow::Borrowed(mode.default_voice());
+                fell_back_to_espeak = true;
+            }
+        }
+
+        let speaking_rate = if fell_back_to_espeak {
+            // Speaking rates are measured differently between modes, so cannot be carried over.
+            mode.speaking_rate_info().map_or(1.0, |info| info.default)
+        } else if let Some(speaking_rate) = overrides.speaking_rate {
+            if let Err(err) = check_speaking_rate(mode, speaking_rate) {
+                ctx.say(err).await?;
+                return Ok(());
+            }
+
+            speaking_rate
+        } else {
+            data.speaking_rate(author.id, mode).await?
+        };
+
+        let (preferred_format, file_extension) = match overrides.format {
+            Some(format) => (format.service_name(), format.file_extension()),
+            None => ("mp3", mode.file_extension()),
         };
 
-        let url = prepare_url(
-            data.tts_services[tts_service_index].clone(),
+        let cache_key = AudioCacheKey::new(
             message,
             &voice,
             mode,
-            &speaking_rate.to_arraystring(),
-            &u64::MAX.to_arraystring(),
+            speaking_rate,
             translation_lang,
+            file_extension,
         );
 
-        let audio = fetch_audio(&data.reqwest, url).await?.try_unwrap()?;
+        let audio = if let Some(audio) = data.audio_cache.get(&cache_key) {
+            audio
+        } else {
+            let tts_service = (!use_local_espeak).then(|| match ctx.guild_id() {
+                Some(guild_id) => data.select_tts_service(guild_id),
+                None => data.any_tts_service(),
+            });
+
+            let supports_format = tts_service.as_ref().is_none_or(|tts_service| {
+                tts_service.capabilities().supports_format(preferred_format)
+            });
+
+            if let Some(format) = overrides.format
+                && !supports_format
+            {
+                let msg = format!(
+                    "**Error**: {} audio cannot be generated right now, please pick another format!",
+                    format.name()
+                );
+
+                ctx.say(msg).await?;
+                return Ok(());
+            }
 
-        let mut file_name = author_name;
-        file_name.push_str(&aformat!(
-            "-{}.{}",
-            ctx.id(),
-            match mode {
-                TTSMode::gTTS | TTSMode::gCloud | TTSMode::Polly => astr!("mp3"),
-                TTSMode::eSpeak => astr!("wav"),
+            let chunks = chunking::split_text(message, MAX_CHUNK_LENGTH);
+            if chunks.len() > 1 && !chunking::can_concat(file_extension) {
+                ctx.say(TOO_LONG_MSG).await?;
+                return Ok(());
             }
-        ));
+
+            let speaking_rate_str = speaking_rate.to_arraystring();
+            let max_length = MAX_ATTACHMENT_LENGTH.to_arraystring();
+            let reqwest = &data.reqwest;
+            let chunk_audio: Vec<_> = stream::iter(chunks)
+                .map(|chunk| {
+                    let (voice, tts_service) = (&*voice, tts_service.as_deref());
+                    let (speaking_rate_str, max_length) = (&speaking_rate_str, &max_length);
+                    async move {
+                        let Some(tts_service) = tts_service else {
+                            return espeak::synthesise(chunk, voice, speaking_rate)
+                                .await
+                                .map(Some);
+                        };
+
+                        let url = prepare_url(
+                            tts_service.url.clone(),
+                            chunk,
+                            voice,
+                            mode,
+                            speaking_rate_str,
+                            max_length,
+                            preferred_format,
+                            translation_lang,
+                        );
+
+                        fetch_audio(reqwest, url).await
+                    }
+                })
+                .buffered(MAX_CONCURRENT_CHUNKS)
+                .try_collect()
+                .await?;
+
+            let audio = chunk_audio.into_iter().collect::<Option<Vec<_>>>();
+            let Some(audio) = audio.and_then(|audio| chunking::concat_audio(file_extension, audio))
+            else {
+                ctx.say(TOO_LONG_MSG).await?;
+                return Ok(());
+            };
+
+            data.audio_cache.insert(cache_key, audio.clone());
+            audio
+        };
+
+        let mut file_name = author_name;
+        file_name.push_str(&aformat!("-{}.", ctx.id()));
+        file_name.push_str(file_extension);
 
         serenity::CreateAttachment::bytes(audio, file_name)
     };
 
+    let content = if fell_back_to_espeak {
+        "Generated some TTS with eSpeak and its default settings, as your TTS mode is unavailable right now!"
+    } else {
+        "Generated some TTS!"
+    };
+
     ctx.send(
         CreateReply::default()
-            .content("Generated some TTS!")
+            .content(content)
             .attachment(attachment),
     )
     .await?;
@@ -162,6 +393,318 @@ async fn tts_(ctx: Context<'_>, author: &serenity::User, message: &str) -> Comma
     Ok(())
 }
 
+/// Joins the bot's voice channel in the guild, or the author's, and queues `request`.
+///
+/// Returns false after replying with an error if the request could not be queued.
+async fn queue_in_vc(
+    ctx: Context<'_>,
+    guild_row: &GuildRow,
+    is_premium: bool,
+    author_name: String,
+    request: voice::GetTTS,
+) -> Result<bool> {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().try_unwrap()?;
+    let voice_context = voice::VCContext {
+        serenity: ctx.serenity_context().clone(),
+        bot_id: ctx.cache().current_user().id,
+        guild_id,
+        channel_id: ctx
+            .author_vc()
+            .map(serenity::ChannelId::get)
+            .map(AtomicU64::new)
+            .map(Arc::new),
+    };
+
+    let connection = match voice::start_connection(&data, voice_context).await {
+        voice::StartConnectionResult::Started(entry)
+        | voice::StartConnectionResult::AlreadyIn(entry) => entry,
+        voice::StartConnectionResult::CannotJoin => {
+            let msg = "I am not in a voice channel, please join one for me to speak in!";
+            ctx.send_error(msg).await?;
+            return Ok(false);
+        }
+        voice::StartConnectionResult::TimedOut => {
+            let msg = "I failed to join your voice channel, please check I have the right permissions and try again!";
+            ctx.send_error(msg).await?;
+            return Ok(false);
+        }
+    };
+
+    let author_id = ctx.author().id;
+    let max_user_queue = guild_row.max_user_queue(IsPremium::from(is_premium));
+    if !connection
+        .pending_counts
+        .try_reserve(author_id, max_user_queue)
+    {
+        let msg = "You have too many messages waiting to be read, please wait for them to finish!";
+        ctx.send_error(msg).await?;
+        return Ok(false);
+    }
+
+    let mode = request.mode;
+    let tx_res = connection
+        .interconnect
+        .unbounded_send(voice::InterconnectMessage::QueueTTS(voice::QueuedTTS {
+            author_id,
+            author_name,
+            settings: guild_row.queue_settings(),
+            request,
+        }));
+
+    if tx_res.is_err() {
+        let msg = "I am leaving the voice channel, please try again in a moment!";
+        ctx.send_error(msg).await?;
+        return Ok(false);
+    }
+
+    data.analytics.log(mode.analytics_event(), false);
+    Ok(true)
+}
+
+/// Replaces the mentions in `text` and lowercases it, like normal messages.
+pub(crate) fn safe_content<'a>(
+    guild: &serenity::Guild,
+    text: &str,
+    mentions: &[serenity::User],
+    attachments: &'a [serenity::Attachment],
+) -> MessageContent<'a> {
+    let options = serenity::ContentSafeOptions::default()
+        .clean_here(false)
+        .clean_everyone(false);
+
+    MessageContent {
+        text: serenity::content_safe(guild, text, options, mentions).to_lowercase(),
+        kind: TTSMessageKind::Default,
+        attachments,
+    }
+}
+
+/// Cleans `content` with `author`'s settings, returning the author's name and the request to
+/// queue.
+///
+/// Returns [`None`] after replying with an error if there is nothing left to read.
+pub(crate) async fn build_request(
+    ctx: Context<'_>,
+    guild_row: &GuildRow,
+    is_premium: bool,
+    author: &serenity::User,
+    member_nick: Option<&str>,
+    mut content: MessageContent<'_>,
+) -> Result<Option<(String, voice::GetTTS)>> {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().try_unwrap()?;
+    let (voice, mode) = data
+        .parse_user_or_guild_with_premium(author.id, Some((guild_id, is_premium)))
+        .await?;
+
+    let nickname_row = data
+        .nickname_db
+        .get([guild_id.into(), author.id.into()])
+        .await?;
+
+    process_msg::clean(
+        &mut content,
+        author,
+        member_nick,
+        &voice,
+        guild_row.xsaid(),
+        guild_row.skip_emoji(),
+        guild_row.repeated_chars,
+        nickname_row.name.as_deref(),
+        &data.regex_cache,
+        || voice::should_announce_name(&data, guild_id, author.id),
+    );
+
+    if content.text.find(|c| !" ?.)'!\":".contains(c)).is_none() {
+        ctx.send_error("That message has nothing to read out!")
+            .await?;
+        return Ok(None);
+    }
+
+    let author_name = nickname_row
+        .name
+        .as_deref()
+        .or(member_nick)
+        .or(author.global_name.as_deref())
+        .unwrap_or(&author.name)
+        .to_owned();
+
+    let request = voice::GetTTS {
+        text: content.text,
+        mode,
+        voice,
+        preferred_format: None,
+        max_length: Some(guild_row.msg_length),
+        speaking_rate: Some(data.speaking_rate(author.id, mode).await?),
+        translation_lang: guild_row
+            .target_lang(IsPremium::from(is_premium))
+            .map(FixedString::from_str_trunc),
+        priority: false,
+    };
+
+    Ok(Some((author_name, request)))
+}
+
+/// Cleans `content` with `author`'s settings, then queues it with [`queue_in_vc`].
+async fn speak_in_vc(
+    ctx: Context<'_>,
+    guild_row: &GuildRow,
+    is_premium: bool,
+    author: &serenity::User,
+    member_nick: Option<&str>,
+    content: MessageContent<'_>,
+) -> Result<bool> {
+    let request = build_request(ctx, guild_row, is_premium, author, member_nick, content).await?;
+    let Some((author_name, request)) = request else {
+        return Ok(false);
+    };
+
+    queue_in_vc(ctx, guild_row, is_premium, author_name, request).await
+}
+
+/// Reads a message aloud in voice, with its author's settings.
+#[poise::command(
+    category = "Extra Commands",
+    hide_in_help,
+    guild_only,
+    context_menu_command = "Read aloud in voice!"
+)]
+pub async fn tts_speak_in_vc(
+    ctx: ApplicationContext<'_>,
+    message: serenity::Message,
+) -> CommandResult {
+    let ctx = Context::from(ctx);
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().try_unwrap()?;
+    let author = &message.author;
+
+    // The same checks as for messages read out as they are sent.
+    let guild_row = data.guilds_db.get(guild_id.into()).await?;
+    let is_bot = author.bot() || message.webhook_id.is_some();
+    if (is_bot && guild_row.bot_ignore())
+        || data.userinfo_db.get(author.id.into()).await?.bot_banned
+    {
+        ctx.send_error("That message cannot be read out!").await?;
+        return Ok(());
+    }
+
+    let is_premium = data.is_premium_simple(ctx.http(), guild_id).await?;
+    let (content, member_nick) = {
+        let guild = require_guild!(ctx);
+        let member_nick = match &message.member {
+            Some(member) => member.nick.clone(),
+            None => guild.members.get(&author.id).and_then(|m| m.nick.clone()),
+        };
+
+        let content = safe_content(
+            &guild,
+            &message.content,
+            &message.mentions,
+            &message.attachments,
+        );
+
+        (content, member_nick)
+    };
+
+    if content.text.len() >= MAX_MESSAGE_LENGTH {
+        ctx.send_error("That message is too long to read out!")
+            .await?;
+        return Ok(());
+    }
+
+    let queued = speak_in_vc(
+        ctx,
+        &guild_row,
+        is_premium,
+        author,
+        member_nick.as_deref(),
+        content,
+    )
+    .await?;
+
+    if queued {
+        let reply = CreateReply::default()
+            .content("Queued that message to be read out!")
+            .ephemeral(true);
+
+        ctx.send(reply).await?;
+    }
+
+    Ok(())
+}
+
+/// Speaks a message in voice, without sending it in chat
+#[poise::command(
+    category = "Extra Commands",
+    hide_in_help,
+    guild_only,
+    slash_command,
+    required_bot_permissions = "SEND_MESSAGES"
+)]
+pub async fn say(
+    ctx: ApplicationContext<'_>,
+    #[description = "The message to speak"] text: String,
+) -> CommandResult {
+    let author = &ctx.interaction.user;
+    let member_nick = ctx
+        .interaction
+        .member
+        .as_deref()
+        .and_then(|member| member.nick.as_deref());
+
+    let ctx = Context::from(ctx);
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().try_unwrap()?;
+    let bot_id = ctx.cache().current_user().id;
+
+    let guild_row = data.guilds_db.get(guild_id.into()).await?;
+    let is_premium = data.is_premium_simple(ctx.http(), guild_id).await?;
+
+    let (content, in_wrong_vc) = {
+        let guild = require_guild!(ctx);
+        let author_vc = guild
+            .voice_states
+            .get(&author.id)
+            .and_then(|vs| vs.channel_id);
+
+        // Matches the `require_voice` handling of normal messages.
+        let in_wrong_vc = guild_row.require_voice()
+            && match guild.voice_states.get(&bot_id) {
+                Some(bot_voice_state) => bot_voice_state.channel_id != author_vc,
+                None => guild_row
+                    .autojoin_channel
+                    .is_some_and(|autojoin_channel| author_vc != Some(autojoin_channel)),
+            };
+
+        (safe_content(&guild, &text, &[], &[]), in_wrong_vc)
+    };
+
+    if in_wrong_vc {
+        let msg = "You need to be in the same voice channel as me to use this!";
+        ctx.send_error(msg).await?;
+        return Ok(());
+    }
+
+    if content.text.len() >= MAX_MESSAGE_LENGTH {
+        ctx.send_error("That message is too long to read out!")
+            .await?;
+        return Ok(());
+    }
+
+    let queued = speak_in_vc(ctx, &guild_row, is_premium, author, member_nick, content).await?;
+
+    if queued {
+        let reply = CreateReply::default()
+            .content("Queued your message to be read out!")
+            .ephemeral(true);
+
+        ctx.send(reply).await?;
+    }
+
+    Ok(())
+}
+
 #[poise::command(
     category = "Extra Commands",
     hide_in_help,
@@ -171,7 +714,8 @@ pub async fn tts_speak_as(
     ctx: ApplicationContext<'_>,
     message: serenity::Message,
 ) -> CommandResult {
-    tts_(ctx.into(), &message.author, &message.content).await
+    let overrides = TTSOverrides::default();
+    tts_(ctx.into(), &message.author, &message.content, overrides).await
 }
 
 #[poise::command(
@@ -180,7 +724,14 @@ pub async fn tts_speak_as(
     context_menu_command = "Speak with your voice!"
 )]
 pub async fn tts_speak(ctx: ApplicationContext<'_>, message: serenity::Message) -> CommandResult {
-    tts_(ctx.into(), &ctx.interaction.user, &message.content).await
+    let overrides = TTSOverrides::default();
+    tts_(
+        ctx.into(),
+        &ctx.interaction.user,
+        &message.content,
+        overrides,
+    )
+    .await
 }
 
 /// Shows various different stats
@@ -334,7 +885,7 @@ pub async fn invite(ctx: Context<'_>) -> CommandResult {
     Ok(())
 }
 
-pub fn commands() -> [Command; 8] {
+pub fn commands() -> [Command; 10] {
     [
         tts(),
         uptime(),
@@ -344,5 +895,7 @@ pub fn commands() -> [Command; 8] {
         invite(),
         tts_speak(),
         tts_speak_as(),
+        tts_speak_in_vc(),
+        say(),
     ]
 }
@@ -18,7 +18,7 @@ use tts_core::{
     common::safe_truncate,
     database,
     database_models::Compact,
-    structs::{Command, CommandResult, Context, Data, TTSModeChoice},
+    structs::{Command, CommandResult, Context, Data, TTSMode},
     voice,
 };
 
@@ -55,23 +55,48 @@ pub async fn user(ctx: Context<'_>, user: i64) -> CommandResult {
 }
 
 #[poise::command(prefix_command, owners_only, hide_in_help)]
-pub async fn guild_voice(ctx: Context<'_>, guild: i64, mode: TTSModeChoice) -> CommandResult {
-    ctx.data()
-        .guild_voice_db
-        .invalidate_cache(&(guild, mode.into()));
+pub async fn guild_voice(ctx: Context<'_>, guild: i64, mode: TTSMode) -> CommandResult {
+    ctx.data().guild_voice_db.invalidate_cache(&(guild, mode));
     ctx.say("Done!").await?;
     Ok(())
 }
 
 #[poise::command(prefix_command, owners_only, hide_in_help)]
-pub async fn user_voice(ctx: Context<'_>, user: i64, mode: TTSModeChoice) -> CommandResult {
-    ctx.data()
-        .user_voice_db
-        .invalidate_cache(&(user, mode.into()));
+pub async fn user_voice(ctx: Context<'_>, user: i64, mode: TTSMode) -> CommandResult {
+    ctx.data().user_voice_db.invalidate_cache(&(user, mode));
     ctx.say("Done!").await?;
     Ok(())
 }
 
+fn format_urls(urls: &[impl std::fmt::Display]) -> String {
+    if urls.is_empty() {
+        String::from("None")
+    } else {
+        urls.iter()
+            .map(|url| format!("`{url}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Reloads the tts-services from `config.toml`, moving voice connections as needed.
+#[poise::command(prefix_command, owners_only, hide_in_help)]
+pub async fn reload_services(ctx: Context<'_>) -> CommandResult {
+    let data = ctx.serenity_context().data::<Data>();
+    let changes = voice::reload_tts_services(&data).await?;
+    let service_count = data.tts_services.read().len();
+
+    ctx.say(format!(
+        "Reloaded {service_count} tts-services!\nAdded: {}\nRemoved: {}\nReweighted: {}",
+        format_urls(&changes.added),
+        format_urls(&changes.removed),
+        format_urls(&changes.reweighted),
+    ))
+    .await?;
+
+    Ok(())
+}
+
 #[poise::command(prefix_command, owners_only, hide_in_help)]
 pub async fn refresh_ofs(ctx: Context<'_>) -> CommandResult {
     let data = ctx.data();
@@ -156,7 +181,9 @@ pub async fn info_(ctx: Context<'_>) -> CommandResult {
         .await?;
 
     let voice_debug = voice::debug_info(&data, guild_id);
-    let tts_service_url = &data.tts_services[data.select_tts_index(guild_id)];
+    let tts_service = data.select_tts_service(guild_id);
+    let tts_service_url = &tts_service.url;
+    let tts_service_capabilities = tts_service.capabilities().to_string();
 
     let embed = CreateEmbed::default()
         .title("TTS Bot Debug Info")
@@ -165,6 +192,7 @@ pub async fn info_(ctx: Context<'_>) -> CommandResult {
 Shard ID: `{shard_id}`
 Voice Connection: `{voice_debug:?}`
 TTS Service URL: `{tts_service_url}`
+TTS Service Capabilities: `{tts_service_capabilities}`
 
 Server Data: `{guild_row:?}`
 User Data: `{user_row:?}`
@@ -304,6 +332,10 @@ fn process_cache_info(
 pub async fn cache_info(ctx: Context<'_>, kind: Option<String>) -> CommandResult {
     ctx.defer().await?;
 
+    if kind.as_deref() == Some("audio") {
+        return audio_cache_info(ctx).await;
+    }
+
     let db_info = if kind.as_deref() == Some("db") {
         let data = ctx.data();
         Some(vec![
@@ -332,6 +364,28 @@ pub async fn cache_info(ctx: Context<'_>, kind: Option<String>) -> CommandResult
     Ok(())
 }
 
+async fn audio_cache_info(ctx: Context<'_>) -> CommandResult {
+    let stats = ctx.data().audio_cache.stats();
+    let hit_rate = match stats.hit_rate() {
+        Some(hit_rate) => Cow::Owned(format!("{:.1}%", hit_rate * 100.0)),
+        None => Cow::Borrowed("N/A"),
+    };
+
+    let size = stats.size_bytes.to_formatted_string(&Locale::en);
+    let embed = CreateEmbed::default()
+        .title("/tts Audio Cache Statistics")
+        .fields([
+            ("Hit rate", format!("`{hit_rate}`"), true),
+            ("Hits", format!("`{}`", stats.hits), true),
+            ("Misses", format!("`{}`", stats.misses), true),
+            ("Entries", format!("`{}`", stats.entry_count), true),
+            ("Size", format!("`{size}b`"), true),
+        ]);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
 fn filter_channels_by<'a>(
     guild: &'a serenity::Guild,
     bot_member: &'a serenity::Member,
@@ -491,11 +545,12 @@ pub async fn guild_info(ctx: Context<'_>, guild_id: Option<serenity::GuildId>) -
     Ok(())
 }
 
-pub fn commands() -> [Command; 6] {
+pub fn commands() -> [Command; 7] {
     [
         debug(),
         register(),
         remove_cache(),
+        reload_services(),
         refresh_ofs(),
         cache_info(),
         guild_info(),
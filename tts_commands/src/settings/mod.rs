@@ -11,28 +11,30 @@ use poise::serenity_prelude as serenity;
 use serenity::{Mentionable, builder::*, small_fixed_array::FixedString};
 
 use tts_core::{
-    common::{confirm_dialog, random_footer},
+    catalogues::VoiceCatalogues,
+    common::{confirm_dialog, push_permission_names, random_footer},
     constants::{GTTS_DISABLED_ERROR, OPTION_SEPERATORS, PREMIUM_NEUTRAL_COLOUR},
     database::{self, Compact},
+    opt_ext::OptionTryUnwrap as _,
     require_guild,
     structs::{
         ApplicationContext, Command, CommandResult, Context, Data, Error, Result, SpeakingRateInfo,
-        TTSMode, TTSModeChoice,
+        TTSMode,
     },
     traits::PoiseContextExt,
 };
 
-use crate::TTS_PREMIUM_ICON_DESC;
+use crate::{REQUIRED_VC_PERMISSIONS, TTS_PREMIUM_ICON_DESC};
 
 use self::voice_paginator::MenuPaginator;
 
-fn format_voice<'a>(data: &Data, voice: &'a str, mode: TTSMode) -> Cow<'a, str> {
+fn format_voice<'a>(catalogues: &VoiceCatalogues, voice: &'a str, mode: TTSMode) -> Cow<'a, str> {
     if mode == TTSMode::gCloud {
         let (lang, variant) = voice.split_once(' ').unwrap();
-        let gender = &data.gcloud_voices[lang][variant];
+        let gender = &catalogues.gcloud_voices[lang][variant];
         Cow::Owned(format!("{lang} - {variant} ({gender})"))
     } else if mode == TTSMode::Polly {
-        let voice = &data.polly_voices[voice];
+        let voice = &catalogues.polly_voices[voice];
         Cow::Owned(format!(
             "{} - {} ({})",
             voice.name, voice.language_name, voice.gender
@@ -72,6 +74,22 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
         none_str
     };
 
+    let autojoin_channel_mention = if let Some(channel) = guild_row.autojoin_channel
+        && require_guild!(ctx).channels.contains_key(&channel)
+    {
+        &*channel.mention().to_arraystring()
+    } else {
+        "author's channel"
+    };
+
+    let home_channel_mention = if let Some(channel) = guild_row.home_channel
+        && require_guild!(ctx).channels.contains_key(&channel)
+    {
+        &*channel.mention().to_arraystring()
+    } else {
+        none_str
+    };
+
     let prefix = &guild_row.prefix;
     let guild_mode = guild_row.voice_mode;
     let nickname = nickname_row.name.as_deref().unwrap_or(none_str);
@@ -90,11 +108,12 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
         .guild_voice_db
         .get((guild_id.into(), guild_mode))
         .await?;
+    let catalogues = data.catalogues();
     let default_voice = {
         if guild_voice_row.guild_id.is_none() {
             Cow::Borrowed(guild_mode.default_voice())
         } else {
-            format_voice(&data, &guild_voice_row.voice, guild_mode)
+            format_voice(&catalogues, &guild_voice_row.voice, guild_mode)
         }
     };
 
@@ -107,7 +126,7 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
             .await?;
 
         match user_voice_row.voice.as_ref() {
-            Some(voice) => format_voice(&data, voice, currently_set_voice_mode),
+            Some(voice) => format_voice(&catalogues, voice, currently_set_voice_mode),
             None => Cow::Borrowed(none_str),
         }
     };
@@ -144,6 +163,20 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
         Some(chars) => &chars.to_arraystring(),
         None => "Disabled",
     };
+    let max_queue_length = match guild_row.max_queue_length {
+        Some(messages) => &aformat!("{} messages", messages.get()),
+        None => "Disabled",
+    };
+    let max_queue_seconds = match guild_row.max_queue_seconds {
+        Some(seconds) => &aformat!("{} seconds", seconds.get()),
+        None => "Disabled",
+    };
+    let queue_catch_up = guild_row.queue_catch_up();
+    let queue_round_robin = guild_row.queue_round_robin();
+    let max_user_queue = match guild_row.max_user_queue {
+        Some(messages) => &aformat!("{} messages", messages.get()),
+        None => "Disabled",
+    };
 
     ctx.send(poise::CreateReply::default().embed(CreateEmbed::default()
         .title("Current Settings")
@@ -156,7 +189,8 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
 {sep1} Setup Channel: {channel_mention}
 {sep1} Required Role: {role_mention}
 {sep1} Command Prefix: `{prefix}`
-{sep1} Auto Join: `{autojoin}`"), false)
+{sep1} Auto Join: `{autojoin}`
+{sep1} Auto Join Channel: {autojoin_channel_mention}"), false)
         .field("**TTS Settings**", format!("
 {sep2} <User> said: message: `{xsaid}`
 {sep2} Ignore bot's messages: `{bot_ignore}`
@@ -171,10 +205,16 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
 
 {sep2} Max Time to Read: `{msg_length} seconds`
 {sep2} Max Repeated Characters: `{repeated_chars}`
+{sep2} Max Queue Length: `{max_queue_length}`
+{sep2} Max Queue Time: `{max_queue_seconds}`
+{sep2} Read names when queue is full: `{queue_catch_up}`
+{sep2} Max Queue Per Person: `{max_user_queue}`
+{sep2} Take turns between people: `{queue_round_robin}`
         "),        false)
-        .field("**Translation Settings (Premium Only)**", format!("
+        .field("**Premium Settings**", format!("
 {sep4} Translation: `{to_translate}`
 {sep4} Translation Language: `{target_lang}`
+{sep4} 24/7 Home Channel: {home_channel_mention}
         ")
         ,false)
         .field("**User Specific**", format!("
@@ -189,32 +229,47 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
     Ok(())
 }
 
-async fn voice_autocomplete<'a>(
+/// The mode picked in the `mode` option of the same command, such as in `/tts`.
+fn chosen_mode(interaction: &serenity::CommandInteraction) -> Option<TTSMode> {
+    let options = interaction.data.options();
+    let option = options.into_iter().find(|option| option.name == "mode")?;
+    match option.value {
+        serenity::ResolvedValue::String(mode) => mode.parse().ok(),
+        _ => None,
+    }
+}
+
+pub(crate) async fn voice_autocomplete<'a>(
     ctx: ApplicationContext<'a>,
     searching: &'a str,
 ) -> serenity::CreateAutocompleteResponse<'a> {
     let data = ctx.data();
-    let Ok((_, mode)) = data
-        .parse_user_or_guild(
+    let mode = if let Some(mode) = chosen_mode(ctx.interaction) {
+        mode
+    } else {
+        let user_or_guild = data.parse_user_or_guild(
             ctx.http(),
             ctx.interaction.user.id,
             ctx.interaction.guild_id,
-        )
-        .await
-    else {
-        return serenity::CreateAutocompleteResponse::new();
+        );
+
+        match user_or_guild.await {
+            Ok((_, mode)) => mode,
+            Err(_) => return serenity::CreateAutocompleteResponse::new(),
+        }
     };
 
+    let catalogues = data.catalogues();
     let voices: &mut dyn Iterator<Item = _> = match mode {
-        TTSMode::gTTS => &mut data
+        TTSMode::gTTS => &mut catalogues
             .gtts_voices
             .iter()
             .map(|(k, v)| (v.to_string(), k.to_string())),
-        TTSMode::eSpeak => &mut data
+        TTSMode::eSpeak => &mut catalogues
             .espeak_voices
             .iter()
             .map(|voice| (voice.to_string(), voice.to_string())),
-        TTSMode::Polly => &mut data.polly_voices.values().map(|voice| {
+        TTSMode::Polly => &mut catalogues.polly_voices.values().map(|voice| {
             let name = format!(
                 "{} - {} ({})",
                 voice.name, voice.language_name, voice.gender
@@ -222,14 +277,21 @@ async fn voice_autocomplete<'a>(
 
             (name, voice.id.to_string())
         }),
-        TTSMode::gCloud => &mut data.gcloud_voices.iter().flat_map(|(language, variants)| {
-            variants.iter().map(move |(variant, gender)| {
-                (
-                    format!("{language} {variant} ({gender})"),
-                    format!("{language} {variant}"),
-                )
-            })
-        }),
+        TTSMode::gCloud => &mut catalogues
+            .gcloud_voices
+            .iter()
+            .flat_map(|(language, variants)| {
+                variants.iter().map(move |(variant, gender)| {
+                    (
+                        format!("{language} {variant} ({gender})"),
+                        format!("{language} {variant}"),
+                    )
+                })
+            }),
+        TTSMode::Custom(_) => &mut catalogues
+            .custom_voices(mode)
+            .iter()
+            .map(|voice| (voice.to_string(), voice.to_string())),
     };
 
     let searching_lower = searching.to_lowercase();
@@ -249,13 +311,35 @@ async fn voice_autocomplete<'a>(
     )
 }
 
+#[expect(clippy::unused_async)]
+pub(crate) async fn mode_autocomplete<'a>(
+    _ctx: ApplicationContext<'a>,
+    searching: &'a str,
+) -> serenity::CreateAutocompleteResponse<'a> {
+    let searching_lower = searching.to_lowercase();
+    let modes = TTSMode::all().filter(|mode| {
+        mode.name().to_lowercase().contains(&searching_lower)
+            || mode
+                .display_name()
+                .to_lowercase()
+                .contains(&searching_lower)
+    });
+
+    serenity::CreateAutocompleteResponse::new().set_choices(
+        modes
+            .take(25)
+            .map(|mode| serenity::AutocompleteChoice::new(mode.display_name(), mode.db_name()))
+            .collect::<Vec<_>>(),
+    )
+}
+
 #[expect(clippy::unused_async)]
 async fn translation_languages_autocomplete<'a>(
     ctx: ApplicationContext<'a>,
     searching: &'a str,
 ) -> serenity::CreateAutocompleteResponse<'a> {
-    let data = ctx.serenity_context().data_ref::<Data>();
-    let languages = data.translation_languages.iter();
+    let catalogues = ctx.serenity_context().data_ref::<Data>().catalogues();
+    let languages = catalogues.translation_languages.iter();
     let mut filtered_languages: Vec<_> = languages
         .filter(|(_, name)| name.starts_with(searching))
         .collect();
@@ -283,7 +367,7 @@ enum Target {
     User,
 }
 
-async fn can_change_mode(
+pub(crate) async fn can_change_mode(
     ctx: &Context<'_>,
     mode: Option<TTSMode>,
     guild_is_premium: bool,
@@ -336,14 +420,15 @@ where
     let (_, mode) = data
         .parse_user_or_guild(ctx.http(), author_id, Some(guild_id))
         .await?;
+    let catalogues = data.catalogues();
     Ok(if let Some(voice) = voice {
-        if check_valid_voice(&data, &voice, mode) {
+        if check_valid_voice(&catalogues, &voice, mode) {
             general_db.create_row(key).await?;
             voice_db
                 .set_one((key, mode), "voice", voice.as_str())
                 .await?;
 
-            let name = get_voice_name(&data, &voice, mode).unwrap_or(&voice);
+            let name = get_voice_name(&catalogues, &voice, mode).unwrap_or(&voice);
             Cow::Owned(match target {
                 Target::Guild => format!("Changed the server voice to: {name}"),
                 Target::User => format!("Changed your voice to {name}"),
@@ -376,22 +461,33 @@ fn format_languages<'a>(mut iter: impl Iterator<Item = &'a FixedString<u8>>) ->
     buf
 }
 
-fn get_voice_name<'a>(data: &'a Data, code: &str, mode: TTSMode) -> Option<&'a FixedString<u8>> {
+fn get_voice_name<'a>(
+    catalogues: &'a VoiceCatalogues,
+    code: &str,
+    mode: TTSMode,
+) -> Option<&'a FixedString<u8>> {
     match mode {
-        TTSMode::gTTS => data.gtts_voices.get(code),
-        TTSMode::Polly => data.polly_voices.get(code).map(|n| &n.name),
-        TTSMode::eSpeak | TTSMode::gCloud => None,
+        TTSMode::gTTS => catalogues.gtts_voices.get(code),
+        TTSMode::Polly => catalogues.polly_voices.get(code).map(|n| &n.name),
+        TTSMode::eSpeak | TTSMode::gCloud | TTSMode::Custom(_) => None,
     }
 }
 
-fn check_valid_voice(data: &Data, code: &FixedString<u8>, mode: TTSMode) -> bool {
+pub(crate) fn check_valid_voice(
+    catalogues: &VoiceCatalogues,
+    code: &FixedString<u8>,
+    mode: TTSMode,
+) -> bool {
     match mode {
-        TTSMode::gTTS | TTSMode::Polly => get_voice_name(data, code, mode).is_some(),
-        TTSMode::eSpeak => data.espeak_voices.contains(code),
+        TTSMode::gTTS | TTSMode::Polly => get_voice_name(catalogues, code, mode).is_some(),
+        TTSMode::eSpeak => catalogues.espeak_voices.contains(code),
         TTSMode::gCloud => code
             .split_once(' ')
-            .and_then(|(language, variant)| data.gcloud_voices.get(language).map(|l| (l, variant)))
+            .and_then(|(language, variant)| {
+                catalogues.gcloud_voices.get(language).map(|l| (l, variant))
+            })
             .is_some_and(|(ls, v)| ls.contains_key(v)),
+        TTSMode::Custom(_) => catalogues.custom_voices(mode).contains(code),
     }
 }
 
@@ -523,6 +619,18 @@ create_bool_command!(
     aliases("translate", "to_translate", "should_translate"),
     check = "crate::premium_command_check",
 );
+create_bool_command!(
+    "Makes the bot read the names of who spoke instead of dropping messages when the queue is full",
+    catch_up,
+    "queue_catch_up",
+    aliases("queue_catch_up", "catchup"),
+);
+create_bool_command!(
+    "Makes the bot take turns reading messages from each person, instead of reading in order",
+    round_robin,
+    "queue_round_robin",
+    aliases("queue_round_robin", "fair_queue"),
+);
 
 /// Changes the required role to use the bot.
 #[poise::command(
@@ -621,6 +729,40 @@ pub async fn required_role(
     Ok(())
 }
 
+/// Changes the role allowed to make announcements, as well as Manage Server.
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("announcer_role", "announcement_role")
+)]
+pub async fn announce_role(
+    ctx: Context<'_>,
+    #[description = "The role allowed to use /announce"] role: Option<serenity::Role>,
+) -> CommandResult {
+    let guild_id = ctx.guild_id().unwrap();
+    ctx.data()
+        .guilds_db
+        .set_one(
+            guild_id.into(),
+            "announce_role",
+            &role.as_ref().map(|r| r.id.get() as i64),
+        )
+        .await?;
+
+    let msg: &str = if let Some(role) = role {
+        &aformat!("{} can now make announcements.", role.mention())
+    } else {
+        "Only members with Manage Server can now make announcements."
+    };
+
+    ctx.say(msg).await?;
+    Ok(())
+}
+
 /// Changes the required prefix for TTS.
 #[poise::command(
     guild_only,
@@ -675,12 +817,13 @@ async fn required_prefix(
 )]
 pub async fn server_mode(
     ctx: Context<'_>,
-    #[description = "The TTS Mode to change to"] mode: Option<TTSModeChoice>,
+    #[description = "The TTS Mode to change to"]
+    #[autocomplete = "mode_autocomplete"]
+    mode: Option<TTSMode>,
 ) -> CommandResult {
     let data = ctx.data();
     let guild_id = ctx.guild_id().unwrap();
 
-    let mode = mode.map(TTSMode::from);
     let guild_is_premium = data.is_premium_simple(ctx.http(), guild_id).await?;
     if !can_change_mode(&ctx, mode, guild_is_premium).await? {
         return Ok(());
@@ -763,7 +906,8 @@ pub async fn translation_lang(
     let guild_id = ctx.guild_id().unwrap().into();
 
     let to_say = if target_lang.as_ref().is_none_or(|target_lang| {
-        data.translation_languages
+        data.catalogues()
+            .translation_languages
             .contains_key(target_lang.as_str())
     }) {
         data.guilds_db
@@ -787,6 +931,145 @@ pub async fn translation_lang(
     Ok(())
 }
 
+/// Checks that both the bot and the author can use `channel`, sending an error if not.
+async fn check_voice_channel(ctx: Context<'_>, channel: &serenity::GuildChannel) -> Result<bool> {
+    let (missing_permissions, author_can_connect) = {
+        let guild = require_guild!(ctx, Ok(false));
+        let bot_id = ctx.cache().current_user().id;
+        let bot_member = guild.members.get(&bot_id).try_unwrap()?;
+
+        let author_permissions = match ctx {
+            Context::Application(poise::ApplicationContext { interaction, .. }) => {
+                let author_member = interaction.member.as_deref().try_unwrap()?;
+                guild.user_permissions_in(channel, author_member)
+            }
+            Context::Prefix(poise::PrefixContext { msg, .. }) => {
+                let author_member = msg.member.as_deref().try_unwrap()?;
+                guild.partial_member_permissions_in(channel, msg.author.id, author_member)
+            }
+        };
+
+        (
+            REQUIRED_VC_PERMISSIONS - guild.user_permissions_in(channel, bot_member),
+            author_permissions.view_channel() && author_permissions.connect(),
+        )
+    };
+
+    if !author_can_connect {
+        ctx.send_error("You cannot join that voice channel yourself!")
+            .await?;
+        return Ok(false);
+    }
+
+    if !missing_permissions.is_empty() {
+        let mut msg =
+            String::from("I do not have permission to TTS in that voice channel, please give me: ");
+        push_permission_names(&mut msg, missing_permissions);
+
+        ctx.send_error(msg).await?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Changes the voice channel the bot automatically joins, instead of the author's
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("autojoin_vc", "auto_join_channel")
+)]
+pub async fn autojoin_channel(
+    ctx: Context<'_>,
+    #[description = "The voice channel to autojoin, leave empty to join the author's channel"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<serenity::GuildChannel>,
+) -> CommandResult {
+    if let Some(channel) = &channel
+        && !check_voice_channel(ctx, channel).await?
+    {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+    data.guilds_db
+        .set_one(
+            guild_id.into(),
+            "autojoin_channel",
+            &channel.as_ref().map(|c| c.id.get() as i64),
+        )
+        .await?;
+
+    let mut msg = if let Some(channel) = channel {
+        format!(
+            "I will now autojoin {} when a message is sent.",
+            channel.mention()
+        )
+    } else {
+        String::from("I will now autojoin the voice channel of the message author.")
+    };
+
+    if !data.guilds_db.get(guild_id.into()).await?.auto_join() {
+        msg.push_str("\nYou may want to enable autojoin with `/set autojoin on`");
+    }
+
+    ctx.say(msg).await?;
+    Ok(())
+}
+
+/// Keeps the bot in a voice channel 24/7, even when nobody is listening
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    check = "crate::premium_command_check",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("home", "247", "24_7")
+)]
+pub async fn home_channel(
+    ctx: Context<'_>,
+    #[description = "The voice channel to stay in, leave empty to disable"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<serenity::GuildChannel>,
+) -> CommandResult {
+    let guild_id = ctx.guild_id().unwrap();
+    if let Some(channel) = &channel
+        && !check_voice_channel(ctx, channel).await?
+    {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+    data.guilds_db
+        .set_one(
+            guild_id.into(),
+            "home_channel",
+            &channel.as_ref().map(|c| c.id.get() as i64),
+        )
+        .await?;
+
+    data.left_home_channels.lock().remove(&guild_id);
+
+    let msg = if let Some(channel) = channel {
+        &aformat!(
+            "I will now stay in {} 24/7, I will join within a minute if I am not in a voice channel.",
+            channel.mention()
+        )
+    } else {
+        "I will no longer stay in a voice channel 24/7."
+    };
+
+    ctx.say(msg).await?;
+    Ok(())
+}
+
 /// Changes the prefix used before commands
 #[poise::command(
     guild_only,
@@ -884,6 +1167,128 @@ pub async fn msg_length(
     Ok(())
 }
 
+/// Changes the max number of messages waiting to be read (0 = off)
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("queue_length", "max_queue")
+)]
+pub async fn max_queue_length(
+    ctx: Context<'_>,
+    #[description = "The max number of queued messages"] messages: u8,
+) -> CommandResult {
+    let to_send = if messages > 100 {
+        "**Error**: Cannot set the max queue length above 100 messages"
+    } else if messages < 3 && messages != 0 {
+        "**Error**: Cannot set the max queue length below 3 messages"
+    } else {
+        ctx.data()
+            .guilds_db
+            .set_one(
+                ctx.guild_id().unwrap().into(),
+                "max_queue_length",
+                &i16::from(messages),
+            )
+            .await?;
+
+        if messages == 0 {
+            "Max queue length is now disabled"
+        } else {
+            &aformat!("Max queue length is now: {messages} messages")
+        }
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
+
+/// Changes the max number of messages each person can have waiting to be read (0 = off)
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("user_queue", "max_queue_per_user")
+)]
+pub async fn max_user_queue(
+    ctx: Context<'_>,
+    #[description = "The max number of queued messages per person"] messages: u8,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    let ceiling = if data.is_premium_simple(ctx.http(), guild_id).await? {
+        database::PREMIUM_MAX_USER_QUEUE
+    } else {
+        database::FREE_MAX_USER_QUEUE
+    };
+
+    let to_send: &str = if u16::from(messages) > ceiling.get() {
+        &aformat!(
+            "**Error**: Cannot set the max queue per person above {} messages",
+            ceiling.get()
+        )
+    } else {
+        data.guilds_db
+            .set_one(guild_id.into(), "max_user_queue", &i16::from(messages))
+            .await?;
+
+        if messages == 0 {
+            "Max queue per person is now disabled"
+        } else {
+            &aformat!("Max queue per person is now: {messages} messages")
+        }
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
+
+/// Changes the max seconds of messages waiting to be read (0 = off)
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("queue_seconds", "max_queue_time")
+)]
+pub async fn max_queue_seconds(
+    ctx: Context<'_>,
+    #[description = "The max seconds of queued messages"] seconds: u16,
+) -> CommandResult {
+    let to_send = if seconds > 600 {
+        "**Error**: Cannot set the max queue time above 600 seconds"
+    } else if seconds < 10 && seconds != 0 {
+        "**Error**: Cannot set the max queue time below 10 seconds"
+    } else {
+        ctx.data()
+            .guilds_db
+            .set_one(
+                ctx.guild_id().unwrap().into(),
+                "max_queue_seconds",
+                &(seconds as i16),
+            )
+            .await?;
+
+        if seconds == 0 {
+            "Max queue time is now disabled"
+        } else {
+            &aformat!("Max queue time is now: {seconds} seconds")
+        }
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
+
 /// Changes the multiplier for how fast to speak
 #[poise::command(
     category = "Settings",
@@ -911,33 +1316,53 @@ pub async fn speaking_rate(
     let (_, mode) = data
         .parse_user_or_guild(ctx.http(), author.id, ctx.guild_id())
         .await?;
+    let speaking_rate_info = match check_speaking_rate(mode, speaking_rate) {
+        Ok(speaking_rate_info) => speaking_rate_info,
+        Err(err) => {
+            ctx.say(err).await?;
+            return Ok(());
+        }
+    };
+
+    data.userinfo_db.create_row(author.id.get() as i64).await?;
+    data.user_voice_db
+        .set_one(
+            (author.id.get() as i64, mode),
+            "speaking_rate",
+            &speaking_rate,
+        )
+        .await?;
+
+    let kind = speaking_rate_info.kind();
+    let msg = aformat!("Your speaking rate is now: {speaking_rate}{kind}");
+    ctx.say(&*msg).await?;
+    Ok(())
+}
+
+/// Checks `speaking_rate` can be used for `mode`, returning the error to show if not.
+pub(crate) fn check_speaking_rate(
+    mode: TTSMode,
+    speaking_rate: f32,
+) -> Result<SpeakingRateInfo, String> {
     let Some(speaking_rate_info) = mode.speaking_rate_info() else {
-        let msg = aformat!("**Error**: Cannot set speaking rate for the {mode} mode");
-        ctx.say(&*msg).await?;
-        return Ok(());
+        return Err(format!(
+            "**Error**: Cannot set speaking rate for the {mode} mode"
+        ));
     };
 
     let kind = speaking_rate_info.kind();
     let SpeakingRateInfo { min, max, .. } = speaking_rate_info;
-    let to_send: &str = if speaking_rate > max {
-        &aformat!("**Error**: Cannot set the speaking rate multiplier above {max}{kind}")
+    if speaking_rate > max {
+        Err(format!(
+            "**Error**: Cannot set the speaking rate multiplier above {max}{kind}"
+        ))
     } else if speaking_rate < min {
-        &aformat!("**Error**: Cannot set the speaking rate multiplier below {min}{kind}")
+        Err(format!(
+            "**Error**: Cannot set the speaking rate multiplier below {min}{kind}"
+        ))
     } else {
-        data.userinfo_db.create_row(author.id.get() as i64).await?;
-        data.user_voice_db
-            .set_one(
-                (author.id.get() as i64, mode),
-                "speaking_rate",
-                &speaking_rate,
-            )
-            .await?;
-
-        &aformat!("Your speaking rate is now: {speaking_rate}{kind}")
-    };
-
-    ctx.say(to_send).await?;
-    Ok(())
+        Ok(speaking_rate_info)
+    }
 }
 
 /// Replaces your username in "<user> said" with a given name
@@ -1006,14 +1431,13 @@ pub async fn nick(
 )]
 pub async fn mode(
     ctx: Context<'_>,
-    #[description = "The TTS Mode to change to, leave blank for server default"] mode: Option<
-        TTSModeChoice,
-    >,
+    #[description = "The TTS Mode to change to, leave blank for server default"]
+    #[autocomplete = "mode_autocomplete"]
+    mode: Option<TTSMode>,
 ) -> CommandResult {
     let data = ctx.data();
     let guild_id = ctx.guild_id().unwrap();
 
-    let mode = mode.map(TTSMode::from);
     let guild_is_premium = data.is_premium_simple(ctx.http(), guild_id).await?;
     if !can_change_mode(&ctx, mode, guild_is_premium).await? {
         return Ok(());
@@ -1085,6 +1509,7 @@ pub async fn voice(
 )]
 pub async fn translation_languages(ctx: Context<'_>) -> CommandResult {
     let data = ctx.data();
+    let catalogues = data.catalogues();
     let author = ctx.author();
     let neutral_colour = ctx.neutral_colour().await;
 
@@ -1103,7 +1528,7 @@ pub async fn translation_languages(ctx: Context<'_>) -> CommandResult {
                 .colour(neutral_colour)
                 .field(
                     "Currently Supported Languages",
-                    format_languages(data.translation_languages.keys()),
+                    format_languages(catalogues.translation_languages.keys()),
                     false,
                 )
                 .author(CreateEmbedAuthor::new(&*author.name).icon_url(author.face()))
@@ -1128,9 +1553,9 @@ pub async fn translation_languages(ctx: Context<'_>) -> CommandResult {
 )]
 pub async fn voices(
     ctx: Context<'_>,
-    #[description = "The mode to see the voices for, leave blank for current"] mode: Option<
-        TTSModeChoice,
-    >,
+    #[description = "The mode to see the voices for, leave blank for current"]
+    #[autocomplete = "mode_autocomplete"]
+    mode: Option<TTSMode>,
 ) -> CommandResult {
     let data = ctx.data();
     let http = ctx.http();
@@ -1139,7 +1564,7 @@ pub async fn voices(
     let guild_id = ctx.guild_id();
 
     let mode = match mode {
-        Some(mode) => TTSMode::from(mode),
+        Some(mode) => mode,
         None => data.parse_user_or_guild(http, author.id, guild_id).await?.1,
     };
 
@@ -1152,9 +1577,11 @@ pub async fn voices(
             Ok(())
         };
 
+        let catalogues = data.catalogues();
         match mode {
-            TTSMode::gTTS => format_languages(data.gtts_voices.keys()),
-            TTSMode::eSpeak => format_languages(data.espeak_voices.iter()),
+            TTSMode::gTTS => format_languages(catalogues.gtts_voices.keys()),
+            TTSMode::eSpeak => format_languages(catalogues.espeak_voices.iter()),
+            TTSMode::Custom(_) => format_languages(catalogues.custom_voices(mode).iter()),
             TTSMode::Polly => {
                 let (current_voice, pages) = list_polly_voices(&ctx).await?;
                 return run_paginator(current_voice, pages).await;
@@ -1203,16 +1630,17 @@ async fn list_polly_voices(ctx: &Context<'_>) -> Result<(String, Box<[String]>)>
     let (voice_id, mode) = data
         .parse_user_or_guild(ctx.http(), ctx.author().id, ctx.guild_id())
         .await?;
+    let catalogues = data.catalogues();
     let voice = match mode {
         TTSMode::Polly => {
             let voice_id: &str = &voice_id;
-            &data.polly_voices[voice_id]
+            &catalogues.polly_voices[voice_id]
         }
-        _ => &data.polly_voices[TTSMode::Polly.default_voice()],
+        _ => &catalogues.polly_voices[TTSMode::Polly.default_voice()],
     };
 
     let mut lang_to_voices: BTreeMap<_, Vec<_>> = BTreeMap::new();
-    for voice in data.polly_voices.values() {
+    for voice in catalogues.polly_voices.values() {
         lang_to_voices
             .entry(&voice.language_name)
             .or_default()
@@ -1254,7 +1682,8 @@ async fn list_gcloud_voices(ctx: &Context<'_>) -> Result<(String, Box<[String]>)
     .split_once(' ')
     .unwrap();
 
-    let pages = data
+    let catalogues = data.catalogues();
+    let pages = catalogues
         .gcloud_voices
         .iter()
         .map(|(language, variants)| {
@@ -1267,7 +1696,7 @@ async fn list_gcloud_voices(ctx: &Context<'_>) -> Result<(String, Box<[String]>)
         })
         .collect::<Result<_>>()?;
 
-    let gender = data.gcloud_voices[lang][variant];
+    let gender = catalogues.gcloud_voices[lang][variant];
     Ok((format!("{lang} {variant} ({gender})"), pages))
 }
 
@@ -1345,15 +1774,23 @@ pub fn commands() -> [Command; 5] {
                 },
                 xsaid(),
                 autojoin(),
+                autojoin_channel(),
                 required_role(),
+                announce_role(),
                 voice(),
                 server_voice(),
                 mode(),
                 server_mode(),
                 msg_length(),
+                max_queue_length(),
+                max_queue_seconds(),
+                catch_up(),
+                max_user_queue(),
+                round_robin(),
                 botignore(),
                 translation(),
                 translation_lang(),
+                home_channel(),
                 speaking_rate(),
                 nick(),
                 repeated_characters(),
@@ -0,0 +1,62 @@
+//! Compares the size and encoding time of each [`WSEncoding`] for typical tts-service messages.
+//!
+//! Run with `cargo bench -p tts_core --bench ws_framing`.
+
+use std::{borrow::Cow, hint::black_box, time::Instant};
+
+use poise::serenity_prelude::{ChannelId, GuildId, UserId, small_fixed_array::FixedString};
+
+use tts_core::{
+    structs::TTSMode,
+    voice::{GetTTS, WSConnectionInfo, WSEncoding, WSMessage, WSMessageFrame},
+};
+
+const ITERATIONS: u32 = 200_000;
+
+fn bench(name: &str, frame: &WSMessageFrame<'_>) {
+    for encoding in [WSEncoding::Json, WSEncoding::MessagePack] {
+        let size = encoding.encode(frame).len();
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            black_box(encoding.encode(black_box(frame)));
+        }
+
+        let per_message = start.elapsed() / ITERATIONS;
+        let encoding = format!("{encoding:?}");
+        println!("{name:<9} {encoding:<11} {size:>4} bytes {per_message:>10.2?}/message");
+    }
+}
+
+fn main() {
+    let guild_id = GuildId::new(1_071_150_374_913_454_161);
+    let connection_info = WSConnectionInfo {
+        channel_id: ChannelId::new(1_071_150_375_483_871_262),
+        endpoint: FixedString::from_static_trunc("c-ams18-7e9b0d2f.discord.media:443"),
+        guild_id,
+        session_id: FixedString::from_static_trunc("0f7d2a6c54be4b3c8b0b3e1d5e7a9c21"),
+        token: FixedString::from_static_trunc("4e5b8f1a9c2d7e3f"),
+        bot_id: UserId::new(513_423_712_582_762_502),
+    };
+
+    let request = GetTTS {
+        text: String::from("Hello everyone, has anyone seen the latest update yet?"),
+        mode: TTSMode::gCloud,
+        voice: Cow::Borrowed("en-US A"),
+        speaking_rate: Some(1.25),
+        max_length: Some(30),
+        preferred_format: None,
+        translation_lang: None,
+        priority: false,
+    };
+
+    let frames = [
+        ("QueueTTS", WSMessage::QueueTTS(request)),
+        ("MoveVC", WSMessage::MoveVC(&connection_info)),
+        ("Leave", WSMessage::Leave),
+    ];
+
+    for (name, inner) in frames {
+        bench(name, &WSMessageFrame { guild_id, inner });
+    }
+}
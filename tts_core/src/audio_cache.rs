@@ -0,0 +1,124 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering::Relaxed},
+    time::Duration,
+};
+
+use poise::serenity_prelude::small_fixed_array::FixedString;
+
+use crate::structs::TTSMode;
+
+const MAX_SIZE_BYTES: u64 = 64 * 1024 * 1024;
+const TIME_TO_LIVE: Duration = Duration::from_mins(10);
+
+/// Everything that changes the audio generated for `/tts`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct AudioCacheKey {
+    pub text: String,
+    pub voice: String,
+    pub mode: TTSMode,
+    /// Stored as bits, as [`f32`] is not [`Eq`].
+    speaking_rate: u32,
+    pub translation_lang: Option<FixedString<u8>>,
+    pub file_extension: &'static str,
+}
+
+impl AudioCacheKey {
+    #[must_use]
+    pub fn new(
+        text: &str,
+        voice: &str,
+        mode: TTSMode,
+        speaking_rate: f32,
+        translation_lang: Option<&str>,
+        file_extension: &'static str,
+    ) -> Self {
+        Self {
+            text: text.to_owned(),
+            voice: voice.to_owned(),
+            mode,
+            speaking_rate: speaking_rate.to_bits(),
+            translation_lang: translation_lang.map(FixedString::from_str_trunc),
+            file_extension,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct AudioCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entry_count: u64,
+    pub size_bytes: u64,
+}
+
+impl AudioCacheStats {
+    /// The fraction of lookups that were hits, or [`None`] if nothing has been looked up.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn hit_rate(self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups != 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+/// Recently generated `/tts` audio, so the same message is only synthesised once.
+///
+/// Bounded by the total size of the audio, instead of the number of entries.
+pub struct AudioCache {
+    cache: mini_moka::sync::Cache<AudioCacheKey, bytes::Bytes>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl AudioCache {
+    #[must_use]
+    pub fn new() -> Self {
+        let cache = mini_moka::sync::Cache::builder()
+            .weigher(|key: &AudioCacheKey, audio: &bytes::Bytes| {
+                u32::try_from(key.text.len() + audio.len()).unwrap_or(u32::MAX)
+            })
+            .max_capacity(MAX_SIZE_BYTES)
+            .time_to_live(TIME_TO_LIVE)
+            .build();
+
+        Self {
+            cache,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &AudioCacheKey) -> Option<bytes::Bytes> {
+        let audio = self.cache.get(key);
+        let counter = if audio.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+
+        counter.fetch_add(1, Relaxed);
+
+        audio
+    }
+
+    pub fn insert(&self, key: AudioCacheKey, audio: bytes::Bytes) {
+        self.cache.insert(key, audio);
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> AudioCacheStats {
+        AudioCacheStats {
+            hits: self.hits.load(Relaxed),
+            misses: self.misses.load(Relaxed),
+            entry_count: self.cache.entry_count(),
+            size_bytes: self.cache.weighted_size(),
+        }
+    }
+}
+
+impl Default for AudioCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use poise::serenity_prelude::{
+    futures,
+    small_fixed_array::{FixedArray, FixedString, TruncatingInto as _},
+};
+
+use crate::{
+    structs::{GoogleGender, GoogleVoice, PollyVoice, Result, TTSMode},
+    voice::TTSService,
+};
+
+/// The voices and translation languages fetched from a tts-service.
+///
+/// Swapped out as a whole when refreshed, so readers should hold onto the [`std::sync::Arc`]
+/// returned by [`crate::structs::Data::catalogues`] instead of fetching it repeatedly.
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct VoiceCatalogues {
+    pub espeak_voices: FixedArray<FixedString<u8>>,
+    pub gtts_voices: BTreeMap<FixedString<u8>, FixedString<u8>>,
+    pub polly_voices: BTreeMap<FixedString<u8>, PollyVoice>,
+    /// {lang_accent: {variant: gender}}
+    pub gcloud_voices: BTreeMap<FixedString<u8>, BTreeMap<FixedString<u8>, GoogleGender>>,
+    /// {mode_name: [voice]}, keyed by name so a removed custom mode does not break snapshots.
+    #[serde(default)]
+    pub custom_voices: BTreeMap<FixedString<u8>, FixedArray<FixedString<u8>>>,
+
+    pub translation_languages: BTreeMap<FixedString<u8>, FixedString<u8>>,
+}
+
+impl VoiceCatalogues {
+    pub async fn fetch(reqwest: &reqwest::Client, tts_service: &TTSService) -> Result<Self> {
+        let custom_modes = TTSMode::all().filter(|mode| matches!(mode, TTSMode::Custom(_)));
+        let custom_voices = futures::future::try_join_all(custom_modes.map(|mode| async move {
+            let voices = fetch_voices::<FixedArray<_>>(reqwest, tts_service, mode).await?;
+            anyhow::Ok((FixedString::from_static_trunc(mode.name()), voices))
+        }));
+
+        let (
+            gtts_voices,
+            espeak_voices,
+            gcloud_voices,
+            polly_voices,
+            custom_voices,
+            translation_languages,
+        ) = tokio::try_join!(
+            fetch_voices(reqwest, tts_service, TTSMode::gTTS),
+            fetch_voices(reqwest, tts_service, TTSMode::eSpeak),
+            fetch_voices(reqwest, tts_service, TTSMode::gCloud),
+            fetch_voices::<Vec<PollyVoice>>(reqwest, tts_service, TTSMode::Polly),
+            custom_voices,
+            fetch_translation_languages(reqwest, tts_service.url.clone()),
+        )?;
+
+        Ok(Self {
+            espeak_voices,
+            gtts_voices,
+            polly_voices: polly_voices
+                .into_iter()
+                .map(|v| (v.id.clone(), v))
+                .collect(),
+            gcloud_voices: prepare_gcloud_voices(gcloud_voices),
+            custom_voices: custom_voices.into_iter().collect(),
+            translation_languages,
+        })
+    }
+
+    /// Fetches the catalogues at startup, falling back to the last snapshot if that fails.
+    pub async fn fetch_or_load_snapshot(
+        reqwest: &reqwest::Client,
+        pool: &sqlx::PgPool,
+        tts_service: &TTSService,
+    ) -> Result<Self> {
+        match Self::fetch(reqwest, tts_service).await {
+            Ok(catalogues) => {
+                if let Err(err) = catalogues.save_snapshot(pool).await {
+                    println!("Failed to save voice catalogue snapshot: {err:?}");
+                }
+
+                println!("Loaded voices and translation languages");
+                Ok(catalogues)
+            }
+            Err(err) => {
+                let Some(catalogues) = Self::load_snapshot(pool).await? else {
+                    return Err(err.context("No voice catalogue snapshot to fall back to"));
+                };
+
+                println!("WARNING: Failed to fetch voices, using the last snapshot: {err:?}");
+                Ok(catalogues)
+            }
+        }
+    }
+
+    /// Saves the catalogues, to be loaded if fetching them fails at the next startup.
+    pub async fn save_snapshot(&self, pool: &sqlx::PgPool) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO catalogue_snapshot (catalogues) VALUES ($1::jsonb)
+            ON CONFLICT (id) DO UPDATE SET catalogues = EXCLUDED.catalogues, saved_at = now()",
+        )
+        .bind(serde_json::to_string(self)?)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Loads the catalogues saved by [`Self::save_snapshot`], if they have ever been saved.
+    pub async fn load_snapshot(pool: &sqlx::PgPool) -> Result<Option<Self>> {
+        let snapshot: Option<String> =
+            sqlx::query_scalar("SELECT catalogues::text FROM catalogue_snapshot")
+                .fetch_optional(pool)
+                .await?;
+
+        snapshot
+            .map(|snapshot| serde_json::from_str(&snapshot).map_err(Into::into))
+            .transpose()
+    }
+
+    /// The codes stored in `user_voice` and `guild_voice` for every voice of `mode`.
+    #[must_use]
+    pub fn voice_codes(&self, mode: TTSMode) -> BTreeSet<String> {
+        match mode {
+            TTSMode::gTTS => self.gtts_voices.keys().map(ToString::to_string).collect(),
+            TTSMode::eSpeak => self.espeak_voices.iter().map(ToString::to_string).collect(),
+            TTSMode::Polly => self.polly_voices.keys().map(ToString::to_string).collect(),
+            TTSMode::gCloud => self
+                .gcloud_voices
+                .iter()
+                .flat_map(|(language, variants)| {
+                    variants
+                        .keys()
+                        .map(move |variant| format!("{language} {variant}"))
+                })
+                .collect(),
+            TTSMode::Custom(_) => {
+                let voices = self.custom_voices(mode).iter();
+                voices.map(ToString::to_string).collect()
+            }
+        }
+    }
+
+    /// The voices of a custom mode, or none if it is not a custom mode.
+    #[must_use]
+    pub fn custom_voices(&self, mode: TTSMode) -> &[FixedString<u8>] {
+        self.custom_voices
+            .get(mode.name())
+            .map(|voices| &voices[..])
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn translation_codes(&self) -> BTreeSet<String> {
+        self.translation_languages
+            .keys()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Keeps the catalogues from `old` that came back empty, as that is a failed fetch or a
+    /// tts-service not supporting a mode, instead of every voice being removed.
+    pub fn fill_empty_from(&mut self, old: &Self) {
+        if self.espeak_voices.is_empty() {
+            self.espeak_voices = old.espeak_voices.clone();
+        }
+        if self.gtts_voices.is_empty() {
+            self.gtts_voices = old.gtts_voices.clone();
+        }
+        if self.polly_voices.is_empty() {
+            self.polly_voices = old.polly_voices.clone();
+        }
+        if self.gcloud_voices.is_empty() {
+            self.gcloud_voices = old.gcloud_voices.clone();
+        }
+        for (mode, voices) in &old.custom_voices {
+            let new_voices = self.custom_voices.entry(mode.clone()).or_default();
+            if new_voices.is_empty() {
+                new_voices.clone_from(voices);
+            }
+        }
+        if self.translation_languages.is_empty() {
+            self.translation_languages = old.translation_languages.clone();
+        }
+    }
+}
+
+async fn fetch_json<T>(reqwest: &reqwest::Client, url: reqwest::Url) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let resp = reqwest.get(url).send().await?;
+    Ok(resp.error_for_status()?.json().await?)
+}
+
+/// Fetches the voices for `mode`, or none if the tts-service does not support it.
+async fn fetch_voices<T: serde::de::DeserializeOwned + Default>(
+    reqwest: &reqwest::Client,
+    tts_service: &TTSService,
+    mode: TTSMode,
+) -> Result<T> {
+    if !tts_service.capabilities().supports_mode(mode) {
+        tracing::warn!("Skipped voices for TTS Mode: {mode}, as {tts_service} does not support it");
+        return Ok(T::default());
+    }
+
+    let mut url = tts_service.url.clone();
+    url.set_path("voices");
+    url.query_pairs_mut()
+        .append_pair("mode", mode.into())
+        .append_pair("raw", "true")
+        .finish();
+
+    fetch_json(reqwest, url).await
+}
+
+async fn fetch_translation_languages(
+    reqwest: &reqwest::Client,
+    mut tts_service: reqwest::Url,
+) -> Result<BTreeMap<FixedString<u8>, FixedString<u8>>> {
+    tts_service.set_path("translation_languages");
+
+    let raw_langs: Vec<(String, FixedString<u8>)> = fetch_json(reqwest, tts_service).await?;
+    let lang_map = raw_langs.into_iter().map(|(mut lang, name)| {
+        lang.make_ascii_lowercase();
+        (lang.trunc_into(), name)
+    });
+
+    Ok(lang_map.collect())
+}
+
+fn prepare_gcloud_voices(
+    raw_map: Vec<GoogleVoice>,
+) -> BTreeMap<FixedString<u8>, BTreeMap<FixedString<u8>, GoogleGender>> {
+    // {lang_accent: {variant: gender}}
+    let mut cleaned_map = BTreeMap::new();
+    for gvoice in raw_map {
+        let variant = gvoice
+            .name
+            .splitn(3, '-')
+            .nth(2)
+            .and_then(|mode_variant| mode_variant.split_once('-'))
+            .filter(|(mode, _)| *mode == "Standard")
+            .map(|(_, variant)| variant);
+
+        if let Some(variant) = variant {
+            let [language] = gvoice.language_codes;
+            cleaned_map
+                .entry(language)
+                .or_insert_with(BTreeMap::new)
+                .insert(FixedString::from_str_trunc(variant), gvoice.ssml_gender);
+        }
+    }
+
+    cleaned_map
+}
@@ -0,0 +1,218 @@
+//! Splitting long text into chunks to be synthesised separately, then joining the audio back up.
+
+const SENTENCE_ENDS: [char; 4] = ['.', '!', '?', '\n'];
+
+/// Splits `text` into chunks of at most `max_length` bytes.
+///
+/// Chunks end after a sentence if possible, otherwise at whitespace, and only split words that
+/// are longer than `max_length` by themselves.
+#[must_use]
+pub fn split_text(text: &str, max_length: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut remaining = text.trim();
+    while remaining.len() > max_length {
+        let mut window_end = max_length;
+        while !remaining.is_char_boundary(window_end) {
+            window_end -= 1;
+        }
+
+        let window = &remaining[..window_end];
+        let split_at = sentence_end(window, remaining)
+            .or_else(|| window.rfind(char::is_whitespace))
+            .filter(|&split_at| split_at != 0)
+            .unwrap_or(window_end);
+
+        let (chunk, rest) = remaining.split_at(split_at);
+        let chunk = chunk.trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+
+        remaining = rest.trim_start();
+    }
+
+    if !remaining.is_empty() {
+        chunks.push(remaining);
+    }
+
+    chunks
+}
+
+/// The end of the last sentence in `window`, which is the start of `text`.
+fn sentence_end(window: &str, text: &str) -> Option<usize> {
+    window
+        .rmatch_indices(SENTENCE_ENDS)
+        .map(|(index, end)| index + end.len())
+        .find(|&end| text[end..].starts_with(char::is_whitespace))
+}
+
+/// If audio with this file extension can be joined by [`concat_audio`].
+#[must_use]
+pub fn can_concat(file_extension: &str) -> bool {
+    matches!(file_extension, "mp3" | "wav")
+}
+
+/// Joins audio synthesised from [`split_text`] chunks, if the format can be joined.
+#[must_use]
+pub fn concat_audio(file_extension: &str, mut chunks: Vec<bytes::Bytes>) -> Option<bytes::Bytes> {
+    if chunks.len() == 1 {
+        return chunks.pop();
+    }
+
+    match file_extension {
+        "mp3" => Some(concat_mp3(&chunks)),
+        "wav" => concat_wav(&chunks),
+        _ => None,
+    }
+}
+
+/// MP3 files are a series of independent frames, so can be joined once any ID3 tags are removed.
+fn concat_mp3(chunks: &[bytes::Bytes]) -> bytes::Bytes {
+    let mut joined = Vec::with_capacity(chunks.iter().map(bytes::Bytes::len).sum());
+    for (i, chunk) in chunks.iter().enumerate() {
+        let frames = if i == 0 { chunk } else { skip_id3v2(chunk) };
+        joined.extend_from_slice(frames);
+    }
+
+    joined.into()
+}
+
+fn skip_id3v2(file: &[u8]) -> &[u8] {
+    let Some(header) = file.get(..10).filter(|header| header.starts_with(b"ID3")) else {
+        return file;
+    };
+
+    // The tag size is stored as four 7 bit bytes, excluding the header.
+    let size = header[6..10]
+        .iter()
+        .fold(0, |size, &byte| (size << 7) | usize::from(byte & 0x7F));
+    file.get(10 + size..).unwrap_or_default()
+}
+
+struct Wav<'a> {
+    fmt: &'a [u8],
+    data: &'a [u8],
+}
+
+fn parse_wav(file: &[u8]) -> Option<Wav<'_>> {
+    if file.get(..4)? != b"RIFF" || file.get(8..12)? != b"WAVE" {
+        return None;
+    }
+
+    let mut fmt = None;
+    let mut offset = 12;
+    while let Some(header) = file.get(offset..offset + 8) {
+        let size = u32::from_le_bytes(header[4..].try_into().unwrap()) as usize;
+        let body_start = offset + 8;
+
+        // Streamed WAV files, such as from `espeak-ng`, cannot know the data size ahead of time.
+        let body_end = body_start.saturating_add(size).min(file.len());
+        let body = &file[body_start..body_end];
+
+        match &header[..4] {
+            b"fmt " => fmt = Some(body),
+            b"data" => {
+                return Some(Wav {
+                    fmt: fmt?,
+                    data: body,
+                });
+            }
+            _ => {}
+        }
+
+        // Chunks are padded to an even length.
+        offset = body_end + (size % 2);
+    }
+
+    None
+}
+
+fn concat_wav(chunks: &[bytes::Bytes]) -> Option<bytes::Bytes> {
+    let wavs: Vec<_> = chunks
+        .iter()
+        .map(|chunk| parse_wav(chunk))
+        .collect::<Option<_>>()?;
+    let fmt = wavs.first()?.fmt;
+    if wavs.iter().any(|wav| wav.fmt != fmt) {
+        return None;
+    }
+
+    let fmt_padding = fmt.len() % 2;
+    let data_length: usize = wavs.iter().map(|wav| wav.data.len()).sum();
+    let riff_length = 4 + (8 + fmt.len() + fmt_padding) + (8 + data_length);
+
+    let mut joined = Vec::with_capacity(8 + riff_length);
+    joined.extend_from_slice(b"RIFF");
+    joined.extend_from_slice(&u32::try_from(riff_length).ok()?.to_le_bytes());
+    joined.extend_from_slice(b"WAVEfmt ");
+    joined.extend_from_slice(&u32::try_from(fmt.len()).ok()?.to_le_bytes());
+    joined.extend_from_slice(fmt);
+    joined.resize(joined.len() + fmt_padding, 0);
+    joined.extend_from_slice(b"data");
+    joined.extend_from_slice(&u32::try_from(data_length).ok()?.to_le_bytes());
+    for wav in wavs {
+        joined.extend_from_slice(wav.data);
+    }
+
+    Some(joined.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{concat_audio, parse_wav, split_text};
+
+    fn wav(samples: &[u8]) -> bytes::Bytes {
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + samples.len() as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16_u32.to_le_bytes());
+        wav.extend_from_slice(&[1, 0, 1, 0, 0x80, 0x3E, 0, 0, 0, 0x7D, 0, 0, 2, 0, 16, 0]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(samples.len() as u32).to_le_bytes());
+        wav.extend_from_slice(samples);
+        wav.into()
+    }
+
+    #[test]
+    fn short_text_is_one_chunk() {
+        assert_eq!(split_text("  Hello there.  ", 100), ["Hello there."]);
+    }
+
+    #[test]
+    fn splits_after_sentences() {
+        let text = "First sentence. Second sentence! Third one? Fourth";
+        assert_eq!(
+            split_text(text, 35),
+            ["First sentence. Second sentence!", "Third one? Fourth"]
+        );
+    }
+
+    #[test]
+    fn ignores_dots_inside_words() {
+        assert_eq!(
+            split_text("Go to example.com and wait", 18),
+            ["Go to example.com", "and wait"]
+        );
+    }
+
+    #[test]
+    fn splits_long_words_on_char_boundaries() {
+        let chunks = split_text("ééééé", 4);
+        assert_eq!(chunks, ["éé", "éé", "é"]);
+    }
+
+    #[test]
+    fn joins_wav_data() {
+        let joined = concat_audio("wav", vec![wav(&[1, 2]), wav(&[3, 4, 5, 6])]).unwrap();
+        let parsed = parse_wav(&joined).unwrap();
+
+        assert_eq!(parsed.data, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(joined, wav(&[1, 2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn cannot_join_opus() {
+        assert!(concat_audio("ogg", vec![b"a"[..].into(), b"b"[..].into()]).is_none());
+    }
+}
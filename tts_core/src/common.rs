@@ -81,6 +81,7 @@ pub async fn fetch_audio(
 }
 
 #[must_use]
+#[allow(clippy::too_many_arguments)]
 pub fn prepare_url(
     mut tts_service: reqwest::Url,
     content: &str,
@@ -88,6 +89,7 @@ pub fn prepare_url(
     mode: TTSMode,
     speaking_rate: &str,
     max_length: &str,
+    preferred_format: &str,
     translation_lang: Option<&str>,
 ) -> reqwest::Url {
     {
@@ -96,7 +98,7 @@ pub fn prepare_url(
         params.append_pair("lang", lang);
         params.append_pair("mode", mode.into());
         params.append_pair("max_length", max_length);
-        params.append_pair("preferred_format", "mp3");
+        params.append_pair("preferred_format", preferred_format);
         params.append_pair("speaking_rate", speaking_rate);
 
         if let Some(translation_lang) = translation_lang {
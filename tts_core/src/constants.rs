@@ -2,6 +2,9 @@ pub const RED: u32 = 0xff0000;
 pub const FREE_NEUTRAL_COLOUR: u32 = 0x3498db;
 pub const PREMIUM_NEUTRAL_COLOUR: u32 = 0xcaa652;
 
+/// The longest message that will be read out in voice, in bytes.
+pub const MAX_MESSAGE_LENGTH: usize = 1500;
+
 pub const OPTION_SEPERATORS: [&str; 4] = [
     ":small_orange_diamond:",
     ":small_blue_diamond:",
@@ -1,14 +1,20 @@
-use std::num::NonZeroU8;
+use std::num::{NonZeroU8, NonZeroU16};
 
 use arrayvec::ArrayString;
 use typesize::derive::TypeSize;
 
 use poise::serenity_prelude::{ChannelId, GuildId, RoleId, UserId};
 
-use crate::structs::{IsPremium, TTSMode};
+use crate::{
+    structs::{IsPremium, TTSMode},
+    voice::QueueSettings,
+};
 
 const MAX_VOICE_LENGTH: usize = 20;
 
+pub const FREE_MAX_USER_QUEUE: NonZeroU16 = NonZeroU16::new(10).unwrap();
+pub const PREMIUM_MAX_USER_QUEUE: NonZeroU16 = NonZeroU16::new(50).unwrap();
+
 fn truncate_convert<const MAX_SIZE: usize>(
     mut s: String,
     field_name: &'static str,
@@ -32,6 +38,9 @@ pub struct GuildRowRaw {
     pub channel: i64,
     pub premium_user: Option<i64>,
     pub required_role: Option<i64>,
+    pub announce_role: Option<i64>,
+    pub home_channel: Option<i64>,
+    pub autojoin_channel: Option<i64>,
     pub xsaid: bool,
     pub auto_join: bool,
     pub bot_ignore: bool,
@@ -40,8 +49,13 @@ pub struct GuildRowRaw {
     pub require_voice: bool,
     pub text_in_voice: bool,
     pub audience_ignore: bool,
+    pub queue_catch_up: bool,
+    pub queue_round_robin: bool,
     pub msg_length: i16,
     pub repeated_chars: i16,
+    pub max_queue_length: i16,
+    pub max_queue_seconds: i16,
+    pub max_user_queue: i16,
     pub prefix: String,
     pub target_lang: Option<String>,
     pub required_prefix: Option<String>,
@@ -54,6 +68,9 @@ pub struct GuildRow {
     pub channel: Option<ChannelId>,
     pub premium_user: Option<UserId>,
     pub required_role: Option<RoleId>,
+    pub announce_role: Option<RoleId>,
+    pub home_channel: Option<ChannelId>,
+    pub autojoin_channel: Option<ChannelId>,
     pub xsaid: bool,
     pub auto_join: bool,
     pub bot_ignore: bool,
@@ -62,8 +79,13 @@ pub struct GuildRow {
     pub require_voice: bool,
     pub text_in_voice: bool,
     pub audience_ignore: bool,
+    pub queue_catch_up: bool,
+    pub queue_round_robin: bool,
     pub msg_length: u16,
     pub repeated_chars: Option<NonZeroU8>,
+    pub max_queue_length: Option<NonZeroU16>,
+    pub max_queue_seconds: Option<NonZeroU16>,
+    pub max_user_queue: Option<NonZeroU16>,
     pub prefix: ArrayString<8>,
     pub target_lang: Option<ArrayString<8>>,
     pub required_prefix: Option<ArrayString<8>>,
@@ -82,6 +104,27 @@ impl GuildRow {
             None
         }
     }
+
+    #[must_use]
+    pub fn queue_settings(&self) -> QueueSettings {
+        QueueSettings {
+            max_messages: self.max_queue_length,
+            max_seconds: self.max_queue_seconds,
+            catch_up: self.queue_catch_up(),
+            round_robin: self.queue_round_robin(),
+        }
+    }
+
+    /// The max number of messages a single user can have queued, capped if premium has lapsed.
+    #[must_use]
+    pub fn max_user_queue(&self, is_premium: IsPremium) -> Option<NonZeroU16> {
+        let max_user_queue = self.max_user_queue?;
+        if is_premium.into() {
+            Some(max_user_queue)
+        } else {
+            Some(max_user_queue.min(FREE_MAX_USER_QUEUE))
+        }
+    }
 }
 
 impl Compact for GuildRowRaw {
@@ -92,8 +135,14 @@ impl Compact for GuildRowRaw {
             channel: (self.channel != 0).then(|| ChannelId::new(self.channel as u64)),
             premium_user: self.premium_user.map(|id| UserId::new(id as u64)),
             required_role: self.required_role.map(|id| RoleId::new(id as u64)),
+            announce_role: self.announce_role.map(|id| RoleId::new(id as u64)),
+            home_channel: self.home_channel.map(|id| ChannelId::new(id as u64)),
+            autojoin_channel: self.autojoin_channel.map(|id| ChannelId::new(id as u64)),
             msg_length: self.msg_length as u16,
             repeated_chars: NonZeroU8::new(self.repeated_chars as u8),
+            max_queue_length: NonZeroU16::new(self.max_queue_length as u16),
+            max_queue_seconds: NonZeroU16::new(self.max_queue_seconds as u16),
+            max_user_queue: NonZeroU16::new(self.max_user_queue as u16),
             prefix: truncate_convert(self.prefix, "guild.prefix"),
             target_lang: self
                 .target_lang
@@ -111,6 +160,8 @@ impl Compact for GuildRowRaw {
         .set_require_voice(self.require_voice)
         .set_text_in_voice(self.text_in_voice)
         .set_audience_ignore(self.audience_ignore)
+        .set_queue_catch_up(self.queue_catch_up)
+        .set_queue_round_robin(self.queue_round_robin)
     }
 }
 
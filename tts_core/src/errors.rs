@@ -15,7 +15,7 @@ use crate::{
     common::{push_permission_names, safe_truncate},
     constants,
     opt_ext::OptionTryUnwrap,
-    structs::{Context, Data},
+    structs::{Context, Data, InvalidTTSMode},
     traits::PoiseContextExt,
 };
 
@@ -311,6 +311,8 @@ async fn handle_argparse(
             "I cannot convert `{}` to a number"
         } else if error.is::<std::str::ParseBoolError>() {
             "I cannot convert `{}` to True/False"
+        } else if error.is::<InvalidTTSMode>() {
+            "`{}` is not a TTS Mode"
         } else {
             "I cannot understand your message"
         };
@@ -0,0 +1,55 @@
+use std::{process::Stdio, time::Duration};
+
+use aformat::ToArrayString as _;
+use tokio::{io::AsyncWriteExt as _, process::Command};
+
+use crate::{
+    opt_ext::OptionTryUnwrap as _,
+    structs::{Result, TTSMode},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Synthesises eSpeak audio as a WAV file with a locally installed `espeak-ng`, without
+/// going through tts-service.
+///
+/// The [`TTSMode::eSpeak`] voices are MBROLA voices, so `en1` is passed as `-v mb-en1`, and
+/// the speaking rate is already in words per minute, so is passed as `-s`.
+pub async fn synthesise(text: &str, voice: &str, speaking_rate: f32) -> Result<bytes::Bytes> {
+    let speaking_rate = match TTSMode::eSpeak.speaking_rate_info() {
+        Some(info) => speaking_rate.clamp(info.min, info.max),
+        None => speaking_rate,
+    };
+
+    let mut child = Command::new("espeak-ng")
+        .args(["--stdout", "--stdin", "-v"])
+        .arg(format!("mb-{voice}"))
+        .arg("-s")
+        .arg((speaking_rate.round() as u16).to_arraystring().as_str())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    // Written at the same time as reading the output, so a full stdout pipe cannot block us.
+    let mut stdin = child.stdin.take().try_unwrap()?;
+    let write_text = async move {
+        stdin.write_all(text.as_bytes()).await?;
+        stdin.shutdown().await
+    };
+
+    let (write_res, output) = tokio::time::timeout(TIMEOUT, async {
+        tokio::join!(write_text, child.wait_with_output())
+    })
+    .await?;
+
+    let output = output?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("espeak-ng exited with {}: {stderr}", output.status);
+    }
+
+    write_res?;
+    Ok(output.stdout.into())
+}
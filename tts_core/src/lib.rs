@@ -2,11 +2,15 @@
 #![feature(type_alias_impl_trait)]
 
 pub mod analytics;
+pub mod audio_cache;
+pub mod catalogues;
+pub mod chunking;
 pub mod common;
 pub mod constants;
 pub mod database;
 pub mod database_models;
 pub mod errors;
+pub mod espeak;
 pub mod macros;
 pub mod opt_ext;
 pub mod process_msg;
@@ -1,7 +1,6 @@
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, HashMap},
-    hash::Hasher as _,
+    collections::{HashMap, HashSet},
     num::NonZeroU8,
     sync::{
         Arc, OnceLock,
@@ -12,7 +11,7 @@ use std::{
 use aformat::{ArrayString, CapStr, aformat};
 pub use anyhow::{Error, Result};
 use dashmap::DashMap;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 use serde::Deserialize as _;
 use strum_macros::IntoStaticStr;
 use tokio::sync::Mutex as TMutex;
@@ -21,10 +20,13 @@ use typesize::derive::TypeSize;
 
 use poise::serenity_prelude::{
     self as serenity, ChannelId, GuildId, RoleId, SkuId, UserId,
-    small_fixed_array::{FixedArray, FixedString},
+    small_fixed_array::{FixedArray, FixedString, TruncatingInto as _},
 };
 
-use crate::{analytics, bool_enum, common::timestamp_in_future, database, voice};
+use crate::{
+    analytics, audio_cache::AudioCache, bool_enum, catalogues::VoiceCatalogues,
+    common::timestamp_in_future, database, voice,
+};
 
 macro_rules! into_static_display {
     ($struct:ident, max_length($len:literal)) => {
@@ -51,6 +53,8 @@ pub struct Config {
     pub main: MainConfig,
     #[serde(rename = "TTS-Services")]
     pub tts_services: FixedArray<TTSServiceConfig, u8>,
+    #[serde(rename = "Custom-Modes", default)]
+    pub custom_modes: Vec<CustomModeConfig>,
     #[serde(rename = "Webhook-Info")]
     pub webhooks: WebhookConfigRaw,
     #[serde(rename = "Website-Info")]
@@ -74,12 +78,24 @@ pub struct MainConfig {
     // Only for situations where gTTS has broken
     #[serde(default)]
     pub gtts_disabled: AtomicBool,
+    /// Makes `/tts` always use the local `espeak-ng`, instead of only when tts-service is down.
+    #[serde(default)]
+    pub force_local_espeak: bool,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(Clone, serde::Deserialize)]
 pub struct TTSServiceConfig {
     pub url: reqwest::Url,
     pub weight: NonZeroU8,
+    /// The number of websocket connections to open, which guilds are spread between.
+    ///
+    /// Defaults to 1, as each connection is health checked and reconnected separately.
+    #[serde(default = "default_ws_connections")]
+    pub connections: NonZeroU8,
+}
+
+const fn default_ws_connections() -> NonZeroU8 {
+    NonZeroU8::MIN
 }
 
 #[derive(serde::Deserialize)]
@@ -203,6 +219,7 @@ pub struct Data {
     pub guild_voice_db: database::Handler<(i64, TTSMode), database::GuildVoiceRowRaw>,
 
     pub entitlement_cache: mini_moka::sync::Cache<UserId, CachedEntitlement>,
+    pub audio_cache: AudioCache,
     pub startup_message: serenity::MessageId,
     pub premium_avatar_url: FixedString<u16>,
     pub system_info: Mutex<sysinfo::System>,
@@ -212,10 +229,13 @@ pub struct Data {
     pub webhooks: WebhookConfig,
     pub pool: sqlx::PgPool,
 
-    pub service_weight_lookups: FixedArray<u8, u8>, // Maps weighted index to non-weighted.
-    pub tts_services: FixedArray<reqwest::Url, u8>,
-    pub ws_connections: FixedArray<voice::LockedWSStream, u8>,
+    /// The tts-services voice connections are spread between, see [`voice::reload_tts_services`].
+    pub tts_services: RwLock<FixedArray<Arc<voice::TTSService>, u8>>,
+    pub tts_services_reload_lock: TMutex<()>,
     pub voice_connections: Mutex<HashMap<serenity::GuildId, voice::ConnectionEntry>>,
+    /// Guilds the bot was told to `/leave`, so their 24/7 home channel is not rejoined until it
+    /// is changed or the bot is told to `/join` again.
+    pub left_home_channels: Mutex<HashSet<serenity::GuildId>>,
 
     pub config: MainConfig,
     pub premium_config: Option<PremiumConfig>,
@@ -225,14 +245,11 @@ pub struct Data {
     pub website_info: Mutex<Option<WebsiteInfo>>,
     pub bot_list_tokens: Mutex<Option<BotListTokens>>,
     pub fully_started: std::sync::atomic::AtomicBool,
+    pub ready_shards: Mutex<HashSet<serenity::ShardId>>,
     pub update_startup_lock: TMutex<()>,
 
-    pub espeak_voices: FixedArray<FixedString<u8>>,
-    pub gtts_voices: BTreeMap<FixedString<u8>, FixedString<u8>>,
-    pub polly_voices: BTreeMap<FixedString<u8>, PollyVoice>,
-    pub gcloud_voices: BTreeMap<FixedString<u8>, BTreeMap<FixedString<u8>, GoogleGender>>,
-
-    pub translation_languages: BTreeMap<FixedString<u8>, FixedString<u8>>,
+    /// Periodically replaced with freshly fetched catalogues, see [`Self::catalogues`].
+    pub catalogues: RwLock<Arc<VoiceCatalogues>>,
 }
 
 impl std::fmt::Debug for Data {
@@ -242,16 +259,42 @@ impl std::fmt::Debug for Data {
 }
 
 impl Data {
+    /// Selects the tts-service for a guild, skipping any services that are failing health checks.
     #[must_use]
-    pub fn select_tts_index(&self, guild_id: serenity::GuildId) -> u8 {
-        let shared_seed = foldhash::SharedSeed::global_random();
-        let mut hasher = foldhash::quality::FoldHasher::with_seed(0, shared_seed);
-        hasher.write_u64(guild_id.get());
-        let bucket_id = hasher.finish();
+    pub fn select_tts_service(&self, guild_id: serenity::GuildId) -> Arc<voice::TTSService> {
+        let services = self.tts_services.read();
+        let mut candidates: Vec<_> = services.iter().filter(|s| s.is_healthy()).collect();
+        if candidates.is_empty() {
+            candidates = services.iter().collect();
+        }
+
+        let weighted = candidates.iter().map(|s| (s.url.as_str(), s.weight()));
+        let index = voice::select_service(guild_id.get(), weighted)
+            .expect("at least one tts-service should be configured");
 
-        self.service_weight_lookups[(bucket_id % u64::from(self.service_weight_lookups.len()))
-            .try_into()
-            .unwrap()]
+        Arc::clone(candidates[index])
+    }
+
+    /// The current voice catalogues, which stay the same for as long as the [`Arc`] is held.
+    #[must_use]
+    pub fn catalogues(&self) -> Arc<VoiceCatalogues> {
+        Arc::clone(&self.catalogues.read())
+    }
+
+    #[must_use]
+    pub fn has_healthy_tts_service(&self) -> bool {
+        self.tts_services.read().iter().any(|s| s.is_healthy())
+    }
+
+    /// Selects any tts-service, for requests that are not tied to a guild.
+    #[must_use]
+    pub fn any_tts_service(&self) -> Arc<voice::TTSService> {
+        let services = self.tts_services.read();
+        let service = services
+            .iter()
+            .find(|s| s.is_healthy())
+            .unwrap_or(&services[0]);
+        Arc::clone(service)
     }
 
     pub async fn speaking_rate(&self, user_id: UserId, mode: TTSMode) -> Result<f32> {
@@ -456,6 +499,17 @@ impl Data {
             }
         }
 
+        let voice = self.resolve_voice(author_id, guild_id, mode).await?;
+        Ok((voice, mode))
+    }
+
+    /// The voice of `author_id` for `mode`, falling back to the server voice then the default.
+    pub async fn resolve_voice(
+        &self,
+        author_id: UserId,
+        guild_id: Option<GuildId>,
+        mode: TTSMode,
+    ) -> Result<Cow<'static, str>> {
         let user_voice_row = self.user_voice_db.get((author_id.into(), mode)).await?;
         let voice =
             // Get user voice for user mode
@@ -473,7 +527,7 @@ impl Data {
                 None
             }.unwrap_or_else(|| Cow::Borrowed(mode.default_voice()));
 
-        Ok((voice, mode))
+        Ok(voice)
     }
 }
 
@@ -502,80 +556,274 @@ impl SpeakingRateInfo {
     }
 }
 
-#[derive(IntoStaticStr, sqlx::Type, TypeSize, Debug, Default, Hash, PartialEq, Eq, Copy, Clone)] //
-#[derive(serde::Serialize)]
+/// The index of a custom mode, in the order they were declared in `config.toml`.
+#[derive(TypeSize, Debug, Hash, PartialEq, Eq, Copy, Clone)]
+pub struct CustomModeId(u8);
+
+/// Filled in by [`TTSMode::register_custom`] before any custom modes can be parsed.
+static CUSTOM_MODES: OnceLock<FixedArray<CustomModeConfig, u8>> = OnceLock::new();
+
+#[derive(TypeSize, Debug, Default, Hash, PartialEq, Eq, Copy, Clone)]
 #[allow(non_camel_case_types)]
-#[sqlx(rename_all = "lowercase")]
-#[sqlx(type_name = "ttsmode")]
 pub enum TTSMode {
     #[default]
     gTTS,
     Polly,
     eSpeak,
     gCloud,
+    /// An engine that tts-service supports beyond the ones above, see [`CustomModeConfig`].
+    Custom(CustomModeId),
 }
 
 impl TTSMode {
+    const BUILTIN: [Self; 4] = [Self::gTTS, Self::eSpeak, Self::gCloud, Self::Polly];
+
+    /// Checks and stores the custom modes from `config.toml`, which can only happen once.
+    pub fn register_custom(modes: Vec<CustomModeConfig>) -> Result<()> {
+        anyhow::ensure!(modes.len() < 256, "Only 255 custom modes can be configured");
+        for (i, mode) in modes.iter().enumerate() {
+            let name = mode.name.as_str();
+            let valid_char = |c: u8| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'_';
+            anyhow::ensure!(
+                (1..=32).contains(&name.len()) && name.bytes().all(valid_char),
+                "Custom mode name `{name}` must be 1-32 lowercase letters, digits or underscores"
+            );
+
+            let taken_by_builtin = Self::BUILTIN
+                .iter()
+                .any(|m| m.name().eq_ignore_ascii_case(name));
+            let taken_by_custom = modes[..i].iter().any(|m| m.name == mode.name);
+            anyhow::ensure!(
+                !taken_by_builtin && !taken_by_custom,
+                "Custom mode name `{name}` is already taken"
+            );
+
+            if let Some(rate) = &mode.speaking_rate {
+                anyhow::ensure!(
+                    rate.min <= rate.default && rate.default <= rate.max && rate.kind.len() <= 32,
+                    "Custom mode `{name}` has an invalid speaking rate"
+                );
+            }
+        }
+
+        CUSTOM_MODES
+            .set(modes.trunc_into())
+            .map_err(|_| anyhow::anyhow!("Custom modes have already been registered"))
+    }
+
+    fn custom_modes() -> &'static [CustomModeConfig] {
+        CUSTOM_MODES
+            .get()
+            .map(|modes| &modes[..])
+            .unwrap_or_default()
+    }
+
+    fn custom_config(id: CustomModeId) -> &'static CustomModeConfig {
+        &Self::custom_modes()[usize::from(id.0)]
+    }
+
+    /// Every mode, built in modes first and then custom modes in the order they were declared.
+    pub fn all() -> impl Iterator<Item = Self> {
+        let custom_ids = (0..u8::MAX).take(Self::custom_modes().len());
+        Self::BUILTIN
+            .into_iter()
+            .chain(custom_ids.map(|id| Self::Custom(CustomModeId(id))))
+    }
+
+    /// The name sent to tts-service and shown to users.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::gTTS => "gTTS",
+            Self::Polly => "Polly",
+            Self::eSpeak => "eSpeak",
+            Self::gCloud => "gCloud",
+            Self::Custom(id) => Self::custom_config(id).name.as_str(),
+        }
+    }
+
+    /// The value of the `TTSMode` Postgres type.
+    #[must_use]
+    pub fn db_name(self) -> &'static str {
+        match self {
+            Self::gTTS => "gtts",
+            Self::Polly => "polly",
+            Self::eSpeak => "espeak",
+            Self::gCloud => "gcloud",
+            Self::Custom(id) => Self::custom_config(id).name.as_str(),
+        }
+    }
+
+    #[must_use]
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::gTTS => "Google Translate TTS (female) (default)",
+            Self::eSpeak => "eSpeak TTS (male)",
+            Self::gCloud => "⭐ gCloud TTS (changeable) ⭐",
+            Self::Polly => "⭐ Amazon Polly TTS (changeable) ⭐",
+            Self::Custom(id) => Self::custom_config(id).display_name.as_str(),
+        }
+    }
+
+    /// The analytics event logged for each message read in this mode.
+    #[must_use]
+    pub fn analytics_event(self) -> Cow<'static, str> {
+        match self {
+            Self::gTTS => Cow::Borrowed("gTTS_tts"),
+            Self::eSpeak => Cow::Borrowed("eSpeak_tts"),
+            Self::gCloud => Cow::Borrowed("gCloud_tts"),
+            Self::Polly => Cow::Borrowed("Polly_tts"),
+            Self::Custom(_) => Cow::Owned(format!("{}_tts", self.name())),
+        }
+    }
+
+    #[must_use]
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::gTTS | Self::gCloud | Self::Polly => "mp3",
+            Self::eSpeak => "wav",
+            Self::Custom(id) => Self::custom_config(id).file_extension.as_str(),
+        }
+    }
+
     #[must_use]
-    pub const fn is_premium(self) -> bool {
+    pub fn is_premium(self) -> bool {
         match self {
             Self::gTTS | Self::eSpeak => false,
             Self::Polly | Self::gCloud => true,
+            Self::Custom(id) => Self::custom_config(id).premium,
         }
     }
 
     #[must_use]
-    pub const fn default_voice(self) -> &'static str {
+    pub fn default_voice(self) -> &'static str {
         match self {
             Self::gTTS => "en",
             Self::eSpeak => "en1",
             Self::Polly => "Brian",
             Self::gCloud => "en-US A",
+            Self::Custom(id) => Self::custom_config(id).default_voice.as_str(),
         }
     }
 
     #[must_use]
-    pub const fn speaking_rate_info(self) -> Option<SpeakingRateInfo> {
+    pub fn speaking_rate_info(self) -> Option<SpeakingRateInfo> {
         match self {
             Self::gTTS => None,
             Self::gCloud => SpeakingRateInfo::new(0.25, 1.0, 4.0, "x"),
             Self::Polly => SpeakingRateInfo::new(10.0, 100.0, 500.0, "%"),
             Self::eSpeak => SpeakingRateInfo::new(100.0, 130.0, 200.0, " words per minute"),
+            Self::Custom(id) => {
+                let rate = Self::custom_config(id).speaking_rate.as_ref()?;
+                SpeakingRateInfo::new(rate.min, rate.default, rate.max, rate.kind.as_str())
+            }
         }
     }
 }
 
-into_static_display!(TTSMode, max_length(6));
+impl From<TTSMode> for &'static str {
+    fn from(mode: TTSMode) -> Self {
+        mode.name()
+    }
+}
 
-#[derive(poise::ChoiceParameter, Clone, Copy)]
-#[allow(non_camel_case_types)]
-pub enum TTSModeChoice {
-    // Name to show in slash command invoke               Aliases for prefix
-    #[name = "Google Translate TTS (female) (default)"]
-    #[name = "gtts"]
-    gTTS,
-    #[name = "eSpeak TTS (male)"]
-    #[name = "espeak"]
-    eSpeak,
-    #[name = "⭐ gCloud TTS (changeable) ⭐"]
-    #[name = "gcloud"]
-    gCloud,
-    #[name = "⭐ Amazon Polly TTS (changeable) ⭐"]
-    #[name = "polly"]
-    Polly,
+impl From<&TTSMode> for &'static str {
+    fn from(mode: &TTSMode) -> Self {
+        mode.name()
+    }
 }
 
-impl From<TTSModeChoice> for TTSMode {
-    fn from(mode: TTSModeChoice) -> Self {
-        match mode {
-            TTSModeChoice::gTTS => Self::gTTS,
-            TTSModeChoice::Polly => Self::Polly,
-            TTSModeChoice::eSpeak => Self::eSpeak,
-            TTSModeChoice::gCloud => Self::gCloud,
-        }
+into_static_display!(TTSMode, max_length(32));
+
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidTTSMode;
+
+impl std::fmt::Display for InvalidTTSMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Unknown TTS mode")
+    }
+}
+
+impl std::error::Error for InvalidTTSMode {}
+
+impl std::str::FromStr for TTSMode {
+    type Err = InvalidTTSMode;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name))
+            .ok_or(InvalidTTSMode)
+    }
+}
+
+impl serde::Serialize for TTSMode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
     }
 }
 
+impl<'de> serde::Deserialize<'de> for TTSMode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = Cow::<str>::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl sqlx::Type<sqlx::Postgres> for TTSMode {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("ttsmode")
+    }
+}
+
+impl sqlx::Encode<'_, sqlx::Postgres> for TTSMode {
+    fn encode_by_ref(
+        &self,
+        buf: &mut sqlx::postgres::PgArgumentBuffer,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        <&str as sqlx::Encode<sqlx::Postgres>>::encode(self.db_name(), buf)
+    }
+}
+
+impl sqlx::Decode<'_, sqlx::Postgres> for TTSMode {
+    fn decode(value: sqlx::postgres::PgValueRef<'_>) -> Result<Self, sqlx::error::BoxDynError> {
+        let name = <&str as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+
+        // Custom modes stay in the Postgres enum after being removed from `config.toml`.
+        Ok(name.parse().unwrap_or_else(|_| {
+            warn!("Unknown TTS mode `{name}` in the database, using the default mode");
+            Self::default()
+        }))
+    }
+}
+
+/// A TTS mode declared in `config.toml`, for engines that tts-service supports beyond the
+/// built in ones. The voices are fetched from tts-service as a list of names, like eSpeak.
+#[derive(serde::Deserialize)]
+pub struct CustomModeConfig {
+    /// Stored in the database and sent to tts-service, so cannot be changed once used.
+    pub name: FixedString<u8>,
+    pub display_name: FixedString<u8>,
+    #[serde(default)]
+    pub premium: bool,
+    pub default_voice: FixedString<u8>,
+    pub speaking_rate: Option<CustomSpeakingRate>,
+    /// The extension of the audio tts-service returns, used for `/tts` attachments.
+    #[serde(default = "default_custom_file_extension")]
+    pub file_extension: FixedString<u8>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct CustomSpeakingRate {
+    pub min: f32,
+    pub default: f32,
+    pub max: f32,
+    pub kind: FixedString<u8>,
+}
+
+fn default_custom_file_extension() -> FixedString<u8> {
+    FixedString::from_static_trunc("wav")
+}
+
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GoogleVoice {
@@ -585,7 +833,7 @@ pub struct GoogleVoice {
     pub language_codes: [FixedString<u8>; 1],
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct PollyVoice {
     pub additional_language_codes: Option<FixedArray<FixedString>>,
     pub language_code: FixedString,
@@ -595,7 +843,7 @@ pub struct PollyVoice {
     pub id: FixedString<u8>,
 }
 
-#[derive(serde::Deserialize, IntoStaticStr, Copy, Clone, Default)]
+#[derive(serde::Serialize, serde::Deserialize, IntoStaticStr, Copy, Clone, Default)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum GoogleGender {
     Male,
@@ -605,7 +853,7 @@ pub enum GoogleGender {
     Unspecified,
 }
 
-#[derive(serde::Deserialize, IntoStaticStr, Copy, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, IntoStaticStr, Copy, Clone)]
 pub enum PollyGender {
     Male,
     Female,
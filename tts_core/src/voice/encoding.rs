@@ -0,0 +1,37 @@
+use tokio_tungstenite::tungstenite::{
+    Message as RawWSMessage, handshake::client::Response, http::header::SEC_WEBSOCKET_PROTOCOL,
+};
+
+/// How messages to a tts-service are encoded, negotiated with the websocket subprotocol.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WSEncoding {
+    /// Sent as text frames, and the only encoding older tts-services understand.
+    #[default]
+    Json,
+    /// Sent as binary frames, with struct fields kept as named map keys.
+    MessagePack,
+}
+
+impl WSEncoding {
+    const MESSAGEPACK_PROTOCOL: &str = "tts-service.msgpack";
+
+    /// The subprotocols offered when connecting, in order of preference.
+    ///
+    /// Agreeing to either means the tts-service understands the hello, see [`super::handshake`].
+    pub const OFFERED_PROTOCOLS: &str = "tts-service.msgpack, tts-service.json";
+
+    pub(super) fn from_response(response: &Response) -> Self {
+        match response.headers().get(SEC_WEBSOCKET_PROTOCOL) {
+            Some(protocol) if protocol == Self::MESSAGEPACK_PROTOCOL => Self::MessagePack,
+            _ => Self::Json,
+        }
+    }
+
+    #[must_use]
+    pub fn encode(self, msg: &impl serde::Serialize) -> RawWSMessage {
+        match self {
+            Self::Json => RawWSMessage::Text(serde_json::to_string(msg).unwrap().into()),
+            Self::MessagePack => RawWSMessage::Binary(rmp_serde::to_vec_named(msg).unwrap().into()),
+        }
+    }
+}
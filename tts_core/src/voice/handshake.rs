@@ -0,0 +1,147 @@
+use std::{borrow::Cow, time::Duration};
+
+use tokio_tungstenite::tungstenite::Message as RawWSMessage;
+
+use poise::serenity_prelude::{
+    futures::{SinkExt as _, StreamExt as _},
+    small_fixed_array::FixedString,
+};
+
+use super::{GetTTS, RawWSStream, WSEncoding};
+use crate::structs::{Result, TTSMode};
+
+/// The version of the websocket protocol the bot speaks, sent in the hello.
+pub const PROTOCOL_VERSION: u16 = 1;
+const HELLO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The first message on a connection, sent before any [`super::WSMessageFrame`].
+#[derive(serde::Serialize)]
+struct ClientHello {
+    protocol_version: u16,
+}
+
+/// The features a tts-service reported in reply to the hello.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ServiceCapabilities {
+    pub protocol_version: u16,
+    #[serde(deserialize_with = "deserialize_known_modes")]
+    pub modes: Vec<TTSMode>,
+    /// The formats that can be requested with [`GetTTS::preferred_format`].
+    pub formats: Vec<FixedString<u8>>,
+    /// If the current message can be stopped without clearing the queue.
+    pub skip: bool,
+    pub pause: bool,
+    /// Negotiated with the websocket subprotocol, not sent in the hello.
+    #[serde(skip)]
+    pub framing: WSEncoding,
+}
+
+/// Skips the modes that are not built in or configured, instead of failing the hello.
+fn deserialize_known_modes<'de, D>(deserializer: D) -> Result<Vec<TTSMode>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let modes = <Vec<String> as serde::Deserialize>::deserialize(deserializer)?;
+    Ok(modes.iter().filter_map(|mode| mode.parse().ok()).collect())
+}
+
+impl ServiceCapabilities {
+    /// The features of tts-services from before the hello, which cannot report their modes, so
+    /// are assumed to support every built in and configured custom mode.
+    #[must_use]
+    pub fn legacy() -> Self {
+        Self {
+            protocol_version: 0,
+            modes: TTSMode::all().collect(),
+            formats: Vec::new(),
+            skip: false,
+            pause: false,
+            framing: WSEncoding::Json,
+        }
+    }
+
+    #[must_use]
+    pub fn supports_mode(&self, mode: TTSMode) -> bool {
+        self.modes.contains(&mode)
+    }
+
+    /// Legacy services do not report their formats, but accept any preferred format.
+    #[must_use]
+    pub fn supports_format(&self, format: &str) -> bool {
+        let mut formats = self.formats.iter();
+        self.protocol_version == 0 || formats.any(|supported| supported.as_str() == format)
+    }
+
+    /// Replaces anything in `request` the tts-service does not support, instead of it failing.
+    pub(super) fn adapt_request(&self, request: &mut GetTTS) {
+        if !self.supports_mode(request.mode)
+            && let Some(&fallback) = self.modes.first()
+        {
+            request.mode = fallback;
+            request.voice = Cow::Borrowed(fallback.default_voice());
+            request.speaking_rate = None;
+        }
+
+        if let Some(format) = &request.preferred_format
+            && !self.supports_format(format)
+        {
+            request.preferred_format = None;
+        }
+    }
+}
+
+impl std::fmt::Display for ServiceCapabilities {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let yes_no = |supported| if supported { "yes" } else { "no" };
+
+        write!(f, "protocol v{}, modes: [", self.protocol_version)?;
+        for (i, mode) in self.modes.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{mode}")?;
+        }
+
+        write!(
+            f,
+            "], formats: {:?}, skip: {}, pause: {}, framing: {:?}",
+            self.formats,
+            yes_no(self.skip),
+            yes_no(self.pause),
+            self.framing,
+        )
+    }
+}
+
+/// Exchanges hellos with a tts-service that has agreed to a subprotocol.
+pub(super) async fn handshake(
+    stream: &mut RawWSStream,
+    encoding: WSEncoding,
+) -> Result<ServiceCapabilities> {
+    let hello = ClientHello {
+        protocol_version: PROTOCOL_VERSION,
+    };
+
+    stream.send(encoding.encode(&hello)).await?;
+    let reply = tokio::time::timeout(HELLO_TIMEOUT, stream.next())
+        .await
+        .map_err(|_| anyhow::anyhow!("tts-service did not reply to hello"))?
+        .ok_or_else(|| anyhow::anyhow!("tts-service closed the connection during hello"))??;
+
+    let mut capabilities: ServiceCapabilities = match reply {
+        RawWSMessage::Text(text) => serde_json::from_str(text.as_str())?,
+        RawWSMessage::Binary(bytes) => rmp_serde::from_slice(&bytes)?,
+        msg => anyhow::bail!("tts-service replied to hello with unexpected message: {msg:?}"),
+    };
+
+    if capabilities.protocol_version < PROTOCOL_VERSION {
+        tracing::warn!(
+            "tts-service speaks protocol v{}, older than v{PROTOCOL_VERSION}",
+            capabilities.protocol_version
+        );
+    }
+
+    capabilities.framing = encoding;
+    Ok(capabilities)
+}
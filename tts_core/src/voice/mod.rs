@@ -9,7 +9,7 @@ use std::{
 use poise::serenity_prelude as serenity;
 use serenity::{
     futures::{
-        self, SinkExt, StreamExt,
+        self, StreamExt,
         channel::{
             mpsc::{UnboundedReceiver, UnboundedSender},
             oneshot,
@@ -17,16 +17,26 @@ use serenity::{
     },
     small_fixed_array::FixedString,
 };
-use tokio::{net::TcpStream, sync::Mutex as TMutex};
-use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, tungstenite::Message as RawWSMessage};
-
-use crate::{
-    structs::Data,
-    voice::models::{WSConnectionInfo, WSMessageFrame},
+use tokio::net::TcpStream;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::structs::Data;
+pub use encoding::WSEncoding;
+pub use handshake::{PROTOCOL_VERSION, ServiceCapabilities};
+pub use models::{GetTTS, WSConnectionInfo, WSMessage, WSMessageFrame};
+pub use queue::{PendingCounts, QueueSettings, QueuedTTS};
+pub(crate) use rendezvous::select as select_service;
+pub use services::{
+    ServiceChanges, TTSService, connect_tts_services, reload_tts_services, set_service_health,
+    spawn_health_check,
 };
-pub use models::{GetTTS, WSMessage};
 
+mod encoding;
+mod handshake;
 mod models;
+mod queue;
+mod rendezvous;
+mod services;
 
 #[derive(Clone, Copy)]
 pub struct LastXsaidInfo {
@@ -44,17 +54,20 @@ impl Default for LastXsaidInfo {
 }
 
 pub type RawWSStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
-pub type LockedWSStream = TMutex<RawWSStream>;
 
-// Do not write to AtomicU64 outside of voice task.
-pub type ConnectionEntry = (
-    UnboundedSender<InterconnectMessage>,
-    Arc<AtomicU64>,
-    LastXsaidInfo,
-);
+#[derive(Clone)]
+pub struct ConnectionEntry {
+    pub interconnect: UnboundedSender<InterconnectMessage>,
+    // Do not write to this outside of voice task.
+    pub channel_id: Arc<AtomicU64>,
+    /// The user ID of the member being followed between channels, or 0 if disabled.
+    pub follow_target: Arc<AtomicU64>,
+    pub pending_counts: PendingCounts,
+    pub xsaid_info: LastXsaidInfo,
+}
 
 pub enum StartConnectionResult {
-    Started(UnboundedSender<InterconnectMessage>),
+    Started(ConnectionEntry),
     TimedOut,
     AlreadyIn(ConnectionEntry),
     /// Only occurs if channel id passed is None.
@@ -86,7 +99,7 @@ impl VCContext {
 }
 
 pub async fn start_connection(data: &Data, ctx: VCContext) -> StartConnectionResult {
-    let (tx, mut rx) = match data.voice_connections.lock().entry(ctx.guild_id) {
+    let (entry, mut rx) = match data.voice_connections.lock().entry(ctx.guild_id) {
         std::collections::hash_map::Entry::Occupied(entry) => {
             return StartConnectionResult::AlreadyIn(entry.get().clone());
         }
@@ -96,13 +109,21 @@ pub async fn start_connection(data: &Data, ctx: VCContext) -> StartConnectionRes
             };
 
             let (tx, rx) = futures::channel::mpsc::unbounded();
-            vacant_entry.insert((tx.clone(), Arc::clone(channel_id), LastXsaidInfo::default()));
+            let entry = vacant_entry.insert(ConnectionEntry {
+                interconnect: tx,
+                channel_id: Arc::clone(channel_id),
+                follow_target: Arc::new(AtomicU64::new(0)),
+                pending_counts: PendingCounts::default(),
+                xsaid_info: LastXsaidInfo::default(),
+            });
 
-            (tx, rx)
+            (entry.clone(), rx)
         }
     };
 
     let (connect_tx, connect_rx) = oneshot::channel::<()>();
+    let follow_target = Arc::clone(&entry.follow_target);
+    let pending_counts = entry.pending_counts.clone();
     tokio::spawn(async move {
         let data = ctx.serenity.data::<Data>();
         let guild_id = ctx.guild_id;
@@ -111,21 +132,191 @@ pub async fn start_connection(data: &Data, ctx: VCContext) -> StartConnectionRes
         //
         // It is important that `rx` is dropped AFTER the leave notifier is triggered, the `rx` drop
         // will any pending leave notifiers and therefore trigger them.
-        let ws_tx = &data.ws_connections[data.select_tts_index(guild_id)];
-        let leave_notifier = ws_task(ctx, ws_tx, &mut rx, connect_tx).await;
+        let leave_notifier =
+            ws_task(ctx, &follow_target, pending_counts, &mut rx, connect_tx).await;
 
+        // Removed first, so nothing is sent to the closed interconnect while the row is deleted.
         data.voice_connections.lock().remove(&guild_id);
+        forget_connection(&data, guild_id).await;
         if let Some(leave_notifier) = leave_notifier {
             leave_notifier.send(()).ok();
         }
     });
 
     match connect_rx.await {
-        Ok(()) => StartConnectionResult::Started(tx),
+        Ok(()) => StartConnectionResult::Started(entry),
         Err(futures::channel::oneshot::Canceled) => StartConnectionResult::TimedOut,
     }
 }
 
+/// Moves the bot out of the audience if it has joined a Stage channel.
+///
+/// With `MUTE_MEMBERS` the bot can unsuppress itself, otherwise it can only raise its hand.
+async fn speak_in_stage(ctx: &VCContext, channel_id: serenity::ChannelId) {
+    let (channel, permissions) = {
+        let Some(guild) = ctx.serenity.cache.guild(ctx.guild_id) else {
+            return;
+        };
+
+        let Some(channel) = guild.channels.get(&channel_id) else {
+            return;
+        };
+
+        if channel.base.kind != serenity::ChannelType::Stage {
+            return;
+        }
+
+        let Some(bot_member) = guild.members.get(&ctx.bot_id) else {
+            return;
+        };
+
+        (
+            channel.clone(),
+            guild.user_permissions_in(channel, bot_member),
+        )
+    };
+
+    let builder = if permissions.mute_members() {
+        serenity::EditVoiceState::new().suppress(false)
+    } else if permissions.request_to_speak() {
+        serenity::EditVoiceState::new().request_to_speak(true)
+    } else {
+        return;
+    };
+
+    if let Err(err) = channel
+        .edit_own_voice_state(&ctx.serenity.http, builder)
+        .await
+    {
+        tracing::warn!("Failed to request to speak in stage {channel_id}: {err}");
+    }
+}
+
+/// Checks if the bot should stay in `channel_id` 24/7, even when nobody is listening.
+async fn is_home_channel(ctx: &VCContext, channel_id: serenity::ChannelId) -> bool {
+    let data = ctx.serenity.data_ref::<Data>();
+    let guild_row = match data.guilds_db.get(ctx.guild_id.into()).await {
+        Ok(guild_row) => guild_row,
+        Err(err) => {
+            tracing::error!("Failed to fetch guild row for {}: {err:?}", ctx.guild_id);
+            return false;
+        }
+    };
+
+    if guild_row.home_channel != Some(channel_id) {
+        return false;
+    }
+
+    match data
+        .is_premium_simple(&ctx.serenity.http, ctx.guild_id)
+        .await
+    {
+        Ok(is_premium) => is_premium,
+        Err(err) => {
+            tracing::error!("Failed to check premium for {}: {err:?}", ctx.guild_id);
+            false
+        }
+    }
+}
+
+/// Records the current voice channel, so the connection can be restored after a restart.
+async fn save_connection(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) {
+    let query = sqlx::query(
+        "INSERT INTO voice_connections(guild_id, channel_id) VALUES ($1, $2)
+        ON CONFLICT (guild_id) DO UPDATE SET channel_id = EXCLUDED.channel_id",
+    );
+
+    let res = query
+        .bind(guild_id.get() as i64)
+        .bind(channel_id.get() as i64)
+        .execute(&data.pool)
+        .await;
+
+    if let Err(err) = res {
+        tracing::error!("Failed to save voice connection for {guild_id}: {err:?}");
+    }
+}
+
+pub async fn forget_connection(data: &Data, guild_id: serenity::GuildId) {
+    let res = sqlx::query("DELETE FROM voice_connections WHERE guild_id = $1")
+        .bind(guild_id.get() as i64)
+        .execute(&data.pool)
+        .await;
+
+    if let Err(err) = res {
+        tracing::error!("Failed to remove saved voice connection for {guild_id}: {err:?}");
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SavedConnectionRow {
+    guild_id: i64,
+    channel_id: i64,
+}
+
+/// Fetches the voice connections that were active for the given shard before the last restart.
+pub async fn fetch_saved_connections(
+    data: &Data,
+    shard_id: serenity::ShardId,
+    shard_count: std::num::NonZeroU16,
+) -> anyhow::Result<Vec<(serenity::GuildId, serenity::ChannelId)>> {
+    let rows: Vec<SavedConnectionRow> = sqlx::query_as(
+        "SELECT guild_id, channel_id FROM voice_connections
+        WHERE (guild_id >> 22) % $1 = $2
+        ORDER BY joined_at",
+    )
+    .bind(i64::from(shard_count.get()))
+    .bind(i64::from(shard_id.0))
+    .fetch_all(&data.pool)
+    .await?;
+
+    let connections = rows.into_iter().map(|row| {
+        (
+            serenity::GuildId::new(row.guild_id as u64),
+            serenity::ChannelId::new(row.channel_id as u64),
+        )
+    });
+
+    Ok(connections.collect())
+}
+
+/// The gap between each [`rejoin`], to stay well below the gateway rate limit.
+pub const REJOIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Joins `channel_id` in the background, for rejoins that have no command to reply to.
+///
+/// `reason` is the kind of channel being rejoined, such as `home channel`, to log the result.
+pub fn rejoin(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    reason: &'static str,
+) {
+    let voice_context = VCContext {
+        serenity: ctx.clone(),
+        channel_id: Some(Arc::new(AtomicU64::new(channel_id.get()))),
+        bot_id: ctx.cache.current_user().id,
+        guild_id,
+    };
+
+    tokio::spawn(async move {
+        let data = voice_context.serenity.data::<Data>();
+        match start_connection(&data, voice_context).await {
+            StartConnectionResult::Started(_) => {
+                tracing::info!("Rejoined {reason} in {guild_id}");
+            }
+            StartConnectionResult::AlreadyIn(_) => {}
+            StartConnectionResult::TimedOut | StartConnectionResult::CannotJoin => {
+                tracing::warn!("Failed to rejoin {reason} in {guild_id}");
+            }
+        }
+    });
+}
+
 #[derive(Clone, Copy)]
 pub enum LeaveVCResult {
     Left,
@@ -139,11 +330,11 @@ pub async fn leave_vc(
     requested_channel_id: Option<serenity::ChannelId>,
 ) -> LeaveVCResult {
     let interconnect = match data.voice_connections.lock().get(&guild_id) {
-        Some((tx, channel_id, _))
+        Some(entry)
             if requested_channel_id
-                .is_none_or(|requested| requested.get() == channel_id.load(SeqCst)) =>
+                .is_none_or(|requested| requested.get() == entry.channel_id.load(SeqCst)) =>
         {
-            tx.clone()
+            entry.interconnect.clone()
         }
         Some(_) => return LeaveVCResult::Mismatch,
         None => return LeaveVCResult::Missing,
@@ -170,8 +361,11 @@ pub fn clear_queue(
     data: &Data,
     guild_id: serenity::GuildId,
 ) -> Result<(), MissingInterconnectError> {
-    if let Some((tx, _, _)) = data.voice_connections.lock().get(&guild_id)
-        && tx.unbounded_send(InterconnectMessage::ClearQueue).is_ok()
+    if let Some(entry) = data.voice_connections.lock().get(&guild_id)
+        && entry
+            .interconnect
+            .unbounded_send(InterconnectMessage::ClearQueue)
+            .is_ok()
     {
         Ok(())
     } else {
@@ -179,13 +373,72 @@ pub fn clear_queue(
     }
 }
 
+/// Queues a message to be read before any others, optionally stopping the current message.
+pub fn queue_priority(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    queued: QueuedTTS,
+    interrupt: bool,
+) -> Result<(), MissingInterconnectError> {
+    let voice_connections = data.voice_connections.lock();
+    let Some(entry) = voice_connections.get(&guild_id) else {
+        return Err(MissingInterconnectError);
+    };
+
+    // Priority messages are not limited, but still need to be tracked to be released.
+    _ = entry.pending_counts.try_reserve(queued.author_id, None);
+
+    let send = |msg| entry.interconnect.unbounded_send(msg).is_ok();
+    if (interrupt && !send(InterconnectMessage::Interrupt))
+        || !send(InterconnectMessage::QueueTTS(queued))
+    {
+        return Err(MissingInterconnectError);
+    }
+
+    Ok(())
+}
+
+/// Sets, or clears, the member the bot should follow between voice channels.
+///
+/// If the new target is already in a different channel, the bot is moved to them immediately.
+pub fn set_follow_target(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    target: Option<(serenity::UserId, serenity::ChannelId)>,
+) -> Result<(), MissingInterconnectError> {
+    let voice_connections = data.voice_connections.lock();
+    let Some(entry) = voice_connections.get(&guild_id) else {
+        return Err(MissingInterconnectError);
+    };
+
+    let Some((user_id, channel_id)) = target else {
+        entry.follow_target.store(0, SeqCst);
+        return Ok(());
+    };
+
+    entry.follow_target.store(user_id.get(), SeqCst);
+    if entry.channel_id.load(SeqCst) != channel_id.get()
+        && entry
+            .interconnect
+            .unbounded_send(InterconnectMessage::MoveVC(channel_id))
+            .is_err()
+    {
+        return Err(MissingInterconnectError);
+    }
+
+    Ok(())
+}
+
 pub fn should_announce_name(
     data: &Data,
     guild_id: serenity::GuildId,
     author_id: serenity::UserId,
 ) -> bool {
     let mut voice_connections = data.voice_connections.lock();
-    let Some((_, _, xsaid)) = voice_connections.get_mut(&guild_id) else {
+    let Some(ConnectionEntry {
+        xsaid_info: xsaid, ..
+    }) = voice_connections.get_mut(&guild_id)
+    else {
         return true;
     };
 
@@ -209,26 +462,36 @@ pub fn debug_info(data: &Data, guild_id: serenity::GuildId) -> Option<VoiceDebug
     data.voice_connections
         .lock()
         .get(&guild_id)
-        .map(|(tx, id, _)| VoiceDebug {
-            is_open: !tx.is_closed(),
-            channel_id: serenity::ChannelId::new(id.load(SeqCst)),
+        .map(|entry| VoiceDebug {
+            is_open: !entry.interconnect.is_closed(),
+            channel_id: serenity::ChannelId::new(entry.channel_id.load(SeqCst)),
         })
 }
 
 #[derive(Debug)]
 pub enum InterconnectMessage {
-    QueueTTS(models::GetTTS),
+    QueueTTS(QueuedTTS),
+    /// Stops the message currently being read, without clearing the queue.
+    Interrupt,
+    MoveVC(serenity::ChannelId),
     Leave(oneshot::Sender<()>),
     ClearQueue,
+    /// The tts-services have been reloaded, or one has started or stopped passing health checks.
+    ServicesChanged {
+        /// A service that has just reconnected, and has therefore lost its voice connections.
+        reconnected: Option<Arc<TTSService>>,
+    },
 }
 
 async fn ws_task(
     ctx: VCContext,
-    ws_tx: &LockedWSStream,
+    follow_target: &Arc<AtomicU64>,
+    pending_counts: PendingCounts,
     interconnect: &mut UnboundedReceiver<InterconnectMessage>,
     connect_tx: oneshot::Sender<()>,
 ) -> Option<oneshot::Sender<()>> {
     let guild_id = ctx.guild_id;
+    let data = ctx.serenity.data_ref::<Data>();
     let end_vc_connection = || {
         send_gateway_message(&ctx, || serenity::ShardRunnerMessage::UpdateVoiceState {
             guild_id,
@@ -238,18 +501,31 @@ async fn ws_task(
         })
     };
 
-    let send_ws_msg = async |inner: WSMessage<'_>| {
-        let msg_framed = WSMessageFrame { guild_id, inner };
-        let serialized = serde_json::to_string(&msg_framed).unwrap();
+    let mut service = data.select_tts_service(guild_id);
+    let send_ws_msg = async |service: &TTSService, inner: WSMessage<'_>| {
+        service.send(&WSMessageFrame { guild_id, inner }).await
+    };
 
-        let msg = RawWSMessage::Text(serialized.into());
-        ws_tx.lock().await.send(msg).await
+    // Marking the service as unhealthy will notify this task to move to another service.
+    let on_send_failure = |service: &Arc<TTSService>, msg_kind: &str| {
+        tracing::error!("Failed to send {msg_kind} message to {service}");
+        set_service_health(data, service, false);
+    };
+
+    let send_tts = async |service: &Arc<TTSService>, mut request: GetTTS| {
+        service.capabilities().adapt_request(&mut request);
+        if send_ws_msg(service, WSMessage::QueueTTS(request))
+            .await
+            .is_err()
+        {
+            on_send_failure(service, "queue");
+        }
     };
 
     let ctx_clone = ctx.clone();
-    let mut collector = create_vc_collector(&ctx_clone);
+    let mut collector = create_vc_collector(&ctx_clone, follow_target);
     let mut connection_info = join_voice_channel(&ctx, &mut collector).await?;
-    if send_ws_msg(WSMessage::MoveVC(&connection_info))
+    if send_ws_msg(&service, WSMessage::MoveVC(&connection_info))
         .await
         .is_err()
     {
@@ -259,41 +535,100 @@ async fn ws_task(
         return None;
     }
 
+    speak_in_stage(&ctx, connection_info.channel_id).await;
+    save_connection(data, guild_id, connection_info.channel_id).await;
+
     // We don't care if the /join has hung up.
     _ = connect_tx.send(());
 
+    let mut queue = queue::TTSQueue::new(pending_counts);
     let mut leave_notifier = None::<oneshot::Sender<()>>;
     loop {
+        let dispatch_at = queue.next_dispatch();
         tokio::select!(
+            () = tokio::time::sleep_until(dispatch_at.unwrap_or_else(tokio::time::Instant::now)), if dispatch_at.is_some() => {
+                if let Some(request) = queue.pop_ready() {
+                    send_tts(&service, request).await;
+                }
+            },
             vc_event = collector.next() => {
                 if let Some(vc_event) = vc_event {
                     match apply_event_to_info(&mut connection_info, vc_event) {
                         ApplyEventResult::LeaveVC => break,
-                        ApplyEventResult::Applied => {
-                            ctx.store_channel_id(connection_info.channel_id);
-                            if send_ws_msg(WSMessage::MoveVC(&connection_info)).await.is_err() {
-                                tracing::error!("Failed to send rejoin message to tts-service");
+                        ApplyEventResult::Lonely => {
+                            if !is_home_channel(&ctx, connection_info.channel_id).await {
                                 break;
                             }
                         },
+                        ApplyEventResult::Applied => {
+                            if ctx.load_channel_id() != connection_info.channel_id {
+                                ctx.store_channel_id(connection_info.channel_id);
+                                speak_in_stage(&ctx, connection_info.channel_id).await;
+                                save_connection(data, guild_id, connection_info.channel_id).await;
+                            }
+
+                            if send_ws_msg(&service, WSMessage::MoveVC(&connection_info)).await.is_err() {
+                                on_send_failure(&service, "rejoin");
+                            }
+                        },
+                        ApplyEventResult::MoveTo(channel_id) => move_voice_channel(&ctx, channel_id).await,
+                        ApplyEventResult::StopFollowing => follow_target.store(0, SeqCst),
                     }
                 } else {
                     // Replace the collector since it seems to have been dropped?
-                    collector = create_vc_collector(&ctx);
+                    collector = create_vc_collector(&ctx, follow_target);
                 }
             },
             inter_msg = interconnect.next() => {
                 match inter_msg {
-                    Some(InterconnectMessage::QueueTTS(request)) => {
-                        if send_ws_msg(WSMessage::QueueTTS(request)).await.is_err() {
-                            tracing::error!("Failed to send queue message to tts-service");
-                            break;
+                    Some(InterconnectMessage::QueueTTS(queued)) => {
+                        if let Some(request) = queue.push(queued) {
+                            send_tts(&service, request).await;
                         }
                     },
+                    Some(InterconnectMessage::Interrupt) => {
+                        // Without skip, the messages sent ahead of time are also cleared and lost.
+                        let msg = if service.capabilities().skip {
+                            queue.interrupt();
+                            WSMessage::Skip
+                        } else {
+                            queue.forget_playing();
+                            WSMessage::ClearQueue
+                        };
+
+                        if send_ws_msg(&service, msg).await.is_err() {
+                            on_send_failure(&service, "interrupt");
+                        }
+                    },
+                    Some(InterconnectMessage::MoveVC(channel_id)) => {
+                        move_voice_channel(&ctx, channel_id).await;
+                    },
                     Some(InterconnectMessage::ClearQueue) => {
-                        if send_ws_msg(WSMessage::ClearQueue).await.is_err() {
-                            tracing::error!("Failed to send clear queue message to tts-service");
-                            break;
+                        queue.clear();
+                        if send_ws_msg(&service, WSMessage::ClearQueue).await.is_err() {
+                            on_send_failure(&service, "clear queue");
+                        }
+                    },
+                    Some(InterconnectMessage::ServicesChanged { reconnected }) => {
+                        let old_service = std::mem::replace(&mut service, data.select_tts_service(guild_id));
+                        let moved = !Arc::ptr_eq(&old_service, &service);
+                        let reconnected = reconnected.is_some_and(|r| Arc::ptr_eq(&r, &service));
+                        if !moved && !reconnected {
+                            continue;
+                        }
+
+                        if moved {
+                            tracing::warn!("Moving {guild_id} from {old_service} to {service}");
+                            // Removed services are still healthy, and are drained by this.
+                            if old_service.is_healthy() {
+                                send_ws_msg(&old_service, WSMessage::Leave).await.ok();
+                            }
+                        }
+
+                        // Anything sent to the old service has been lost, so the queue can continue now.
+                        queue.forget_playing();
+                        if send_ws_msg(&service, WSMessage::MoveVC(&connection_info)).await.is_err() {
+                            on_send_failure(&service, "rejoin");
                         }
                     },
                     Some(InterconnectMessage::Leave(notifier)) => {
@@ -310,7 +645,7 @@ async fn ws_task(
 
     end_vc_connection().await;
 
-    send_ws_msg(WSMessage::Leave).await.ok();
+    send_ws_msg(&service, WSMessage::Leave).await.ok();
     leave_notifier
 }
 
@@ -329,10 +664,42 @@ enum VCEvent {
     ChannelDeleted,
     State(StateEvent),
     Server(ServerEvent),
+    FollowMoved(serenity::ChannelId),
+    FollowLeft,
+}
+
+/// Checks if `leaving_user` leaving `target_channel` would leave only bots behind.
+fn is_lonely_after_leave(
+    guild: &serenity::Guild,
+    target_channel: u64,
+    leaving_user: serenity::UserId,
+) -> bool {
+    let mut channel_voice_states = guild.voice_states.iter().filter(|vs| {
+        vs.channel_id
+            .is_some_and(|vs_channel| vs_channel == target_channel)
+    });
+
+    let any_non_leaving_non_bot_member = channel_voice_states.any(|voice_state| {
+        if voice_state.user_id == leaving_user {
+            return false;
+        }
+
+        if let Some(member) = guild.members.get(&voice_state.user_id) {
+            !member.user.bot()
+        } else {
+            false
+        }
+    });
+
+    !any_non_leaving_non_bot_member
 }
 
-fn create_vc_collector(ctx: &VCContext) -> impl futures::Stream<Item = VCEvent> {
+fn create_vc_collector(
+    ctx: &VCContext,
+    follow_target: &Arc<AtomicU64>,
+) -> impl futures::Stream<Item = VCEvent> {
     let target_channel = Arc::clone(ctx.raw_channel_id());
+    let follow_target = Arc::clone(follow_target);
     let cache = ctx.serenity.cache.clone();
     let guild_id = ctx.guild_id;
     let bot_id = ctx.bot_id;
@@ -355,33 +722,26 @@ fn create_vc_collector(ctx: &VCContext) -> impl futures::Stream<Item = VCEvent>
                     session_id: event.session_id.clone(),
                     channel_id: event.channel_id,
                 }))
+            } else if event.user_id.get() == follow_target.load(SeqCst) {
+                let target_channel = target_channel.load(SeqCst);
+                match event.channel_id {
+                    Some(new_channel) if new_channel == target_channel => None,
+                    Some(new_channel) => Some(VCEvent::FollowMoved(new_channel)),
+                    None => {
+                        let guild = cache.guild(guild_id)?;
+                        if is_lonely_after_leave(&guild, target_channel, event.user_id) {
+                            Some(VCEvent::Lonely)
+                        } else {
+                            Some(VCEvent::FollowLeft)
+                        }
+                    }
+                }
             } else if let Some(guild) = cache.guild(guild_id)
                 && let Some(old_state) = guild.voice_states.get(&event.user_id)
                 && old_state.channel_id.is_some() & event.channel_id.is_none()
+                && is_lonely_after_leave(&guild, target_channel.load(SeqCst), event.user_id)
             {
-                let target_channel = target_channel.load(SeqCst);
-                let mut channel_voice_states = guild.voice_states.iter().filter(|vs| {
-                    vs.channel_id
-                        .is_some_and(|vs_channel| vs_channel == target_channel)
-                });
-
-                let any_non_leaving_non_bot_member = channel_voice_states.any(|voice_state| {
-                    if voice_state.user_id == event.user_id {
-                        return false;
-                    }
-
-                    if let Some(member) = guild.members.get(&voice_state.user_id) {
-                        !member.user.bot()
-                    } else {
-                        false
-                    }
-                });
-
-                if any_non_leaving_non_bot_member {
-                    None
-                } else {
-                    Some(VCEvent::Lonely)
-                }
+                Some(VCEvent::Lonely)
             } else {
                 None
             }
@@ -453,6 +813,8 @@ async fn join_voice_channel(
                     state = Some(event);
                 }
             }
+            // Following cannot have been enabled before the connection is established.
+            VCEvent::FollowMoved(_) | VCEvent::FollowLeft => {}
         }
 
         if let Some(state) = &mut state
@@ -473,10 +835,24 @@ async fn join_voice_channel(
     None
 }
 
+async fn move_voice_channel(ctx: &VCContext, channel_id: serenity::ChannelId) {
+    // The resulting voice state and server updates are handled by `apply_event_to_info`.
+    send_gateway_message(ctx, || serenity::ShardRunnerMessage::UpdateVoiceState {
+        guild_id: ctx.guild_id,
+        channel_id: Some(channel_id),
+        self_mute: false,
+        self_deaf: false,
+    })
+    .await;
+}
+
 #[derive(Clone, Copy)]
 enum ApplyEventResult {
     Applied,
     LeaveVC,
+    Lonely,
+    MoveTo(serenity::ChannelId),
+    StopFollowing,
 }
 
 fn apply_event_to_info(connection_info: &mut WSConnectionInfo, event: VCEvent) -> ApplyEventResult {
@@ -498,6 +874,9 @@ fn apply_event_to_info(connection_info: &mut WSConnectionInfo, event: VCEvent) -
             connection_info.endpoint = endpoint;
             ApplyEventResult::Applied
         }
-        VCEvent::Lonely | VCEvent::ChannelDeleted => ApplyEventResult::LeaveVC,
+        VCEvent::FollowMoved(channel_id) => ApplyEventResult::MoveTo(channel_id),
+        VCEvent::FollowLeft => ApplyEventResult::StopFollowing,
+        VCEvent::Lonely => ApplyEventResult::Lonely,
+        VCEvent::ChannelDeleted => ApplyEventResult::LeaveVC,
     }
 }
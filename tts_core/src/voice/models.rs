@@ -31,10 +31,12 @@ pub enum WSMessage<'a> {
     QueueTTS(GetTTS),
     MoveVC(&'a WSConnectionInfo),
     ClearQueue,
+    /// Stops the current message, only sent if [`super::ServiceCapabilities::skip`] is set.
+    Skip,
     Leave,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct GetTTS {
     pub text: String,
     pub mode: TTSMode,
@@ -47,6 +49,9 @@ pub struct GetTTS {
     pub preferred_format: Option<FixedString<u8>>,
     #[serde(default)]
     pub translation_lang: Option<FixedString<u8>>,
+    /// Places the message at the front of the queue, this is handled before tts-service.
+    #[serde(skip)]
+    pub priority: bool,
 }
 
 #[derive(serde::Serialize)]
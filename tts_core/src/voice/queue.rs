@@ -0,0 +1,612 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    num::NonZeroU16,
+    sync::Arc,
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use tokio::time::Instant;
+
+use poise::serenity_prelude::UserId;
+
+use super::GetTTS;
+
+/// A rough average reading speed at the default speaking rate, as tts-service does not report
+/// playback progress.
+const CHARACTERS_PER_SECOND: f32 = 14.0;
+/// How long before the estimated end of playback to send the next message, to avoid gaps.
+const DISPATCH_LOOKAHEAD: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueueSettings {
+    pub max_messages: Option<NonZeroU16>,
+    pub max_seconds: Option<NonZeroU16>,
+    /// If the names of the authors should be read instead of dropping the oldest messages.
+    pub catch_up: bool,
+    /// If messages should alternate between authors, instead of being read in order.
+    pub round_robin: bool,
+}
+
+#[derive(Debug)]
+pub struct QueuedTTS {
+    pub author_id: UserId,
+    pub author_name: String,
+    pub settings: QueueSettings,
+    pub request: GetTTS,
+}
+
+/// The number of messages each user has waiting in the queue, shared with message handling.
+#[derive(Clone, Default)]
+pub struct PendingCounts(Arc<Mutex<HashMap<UserId, u16>>>);
+
+impl PendingCounts {
+    /// Reserves a space in the queue for `user_id`, returning false if they have hit `max_pending`.
+    #[must_use]
+    pub fn try_reserve(&self, user_id: UserId, max_pending: Option<NonZeroU16>) -> bool {
+        let mut counts = self.0.lock();
+        let count = counts.entry(user_id).or_default();
+        if max_pending.is_some_and(|max| *count >= max.get()) {
+            return false;
+        }
+
+        *count += 1;
+        true
+    }
+
+    fn release(&self, user_id: UserId) {
+        let mut counts = self.0.lock();
+        if let Some(count) = counts.get_mut(&user_id) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                counts.remove(&user_id);
+            }
+        }
+    }
+
+    fn clear(&self) {
+        self.0.lock().clear();
+    }
+}
+
+/// Roughly how long `request` will take to read.
+///
+/// Translated messages are estimated from the original text, as the translation is not known.
+#[expect(clippy::cast_precision_loss)]
+fn estimate_duration(request: &GetTTS) -> Duration {
+    let speed = match (request.speaking_rate, request.mode.speaking_rate_info()) {
+        (Some(speaking_rate), Some(info)) if speaking_rate > 0.0 && info.default > 0.0 => {
+            speaking_rate / info.default
+        }
+        _ => 1.0,
+    };
+
+    let mut estimate = request.text.chars().count() as f32 / (CHARACTERS_PER_SECOND * speed);
+    if let Some(max_length) = request.max_length {
+        estimate = estimate.min(f32::from(max_length));
+    }
+
+    Duration::from_secs_f32(estimate)
+}
+
+struct PendingTTS {
+    author_id: UserId,
+    author_name: String,
+    duration: Duration,
+    request: GetTTS,
+}
+
+/// A message that has been sent to tts-service, and is estimated to still be playing.
+struct PlayingTTS {
+    /// The author to release the pending count of, or [`None`] for a catch up summary.
+    author_id: Option<UserId>,
+    until: Instant,
+}
+
+struct CatchUp {
+    names: Vec<String>,
+    /// The latest request, used for the voice settings of the summary.
+    template: GetTTS,
+}
+
+impl CatchUp {
+    fn add_name(&mut self, name: String) {
+        if !self.names.contains(&name) {
+            self.names.push(name);
+        }
+    }
+
+    fn into_summary(self) -> GetTTS {
+        let text = match self.names.as_slice() {
+            [] => String::new(),
+            [name] => format!("{name} said things"),
+            [first, second] => format!("{first} and {second} said things"),
+            [first, second, third] => format!("{first}, {second} and {third} said things"),
+            [first, second, others @ ..] => {
+                format!("{first}, {second} and {} others said things", others.len())
+            }
+        };
+
+        GetTTS {
+            text,
+            translation_lang: None,
+            ..self.template
+        }
+    }
+}
+
+/// The messages waiting to be sent to tts-service for a single guild.
+///
+/// Messages are sent straight away if nothing is being read, otherwise they are held back until
+/// the current message is nearly finished, so priority messages, limits and round robin can
+/// still reorder or drop them.
+pub(super) struct TTSQueue {
+    pending: VecDeque<PendingTTS>,
+    pending_counts: PendingCounts,
+    playing: VecDeque<PlayingTTS>,
+    /// When the messages already sent to tts-service are estimated to finish playing.
+    playing_until: Instant,
+    /// The names of the authors of skipped messages, read before any newer messages.
+    catch_up: Option<CatchUp>,
+    round_robin: bool,
+    /// The dispatch number each author was last served at, for round robin.
+    last_served: HashMap<UserId, u64>,
+    dispatch_count: u64,
+}
+
+impl TTSQueue {
+    pub fn new(pending_counts: PendingCounts) -> Self {
+        Self {
+            pending: VecDeque::new(),
+            pending_counts,
+            playing: VecDeque::new(),
+            playing_until: Instant::now(),
+            catch_up: None,
+            round_robin: false,
+            last_served: HashMap::new(),
+            dispatch_count: 0,
+        }
+    }
+
+    fn backlog(&self, now: Instant) -> Duration {
+        let pending: Duration = self.pending.iter().map(|p| p.duration).sum();
+        pending + self.playing_until.saturating_duration_since(now)
+    }
+
+    fn is_over_limits(&self, settings: QueueSettings, now: Instant) -> bool {
+        let max_messages = settings.max_messages.map(|m| usize::from(m.get()));
+        let max_seconds = settings
+            .max_seconds
+            .map(|s| Duration::from_secs(s.get().into()));
+
+        max_messages.is_some_and(|max| self.pending.len() > max)
+            || max_seconds.is_some_and(|max| self.backlog(now) > max)
+    }
+
+    /// Adds a message to the queue, returning it if it should be sent to tts-service immediately.
+    pub fn push(&mut self, queued: QueuedTTS) -> Option<GetTTS> {
+        self.push_at(queued, Instant::now())
+    }
+
+    fn push_at(&mut self, queued: QueuedTTS, now: Instant) -> Option<GetTTS> {
+        let QueuedTTS {
+            author_id,
+            author_name,
+            settings,
+            request,
+        } = queued;
+
+        let pending = PendingTTS {
+            duration: estimate_duration(&request),
+            author_id,
+            author_name,
+            request,
+        };
+
+        self.round_robin = settings.round_robin;
+        let is_idle = now + DISPATCH_LOOKAHEAD >= self.playing_until;
+        if is_idle && self.pending.is_empty() && self.catch_up.is_none() {
+            self.dispatch(Some(pending.author_id), pending.duration, now);
+            return Some(pending.request);
+        }
+
+        if pending.request.priority {
+            // Priority messages skip the limits, but stay in order with each other.
+            let index = self
+                .pending
+                .iter()
+                .take_while(|p| p.request.priority)
+                .count();
+            self.pending.insert(index, pending);
+            return None;
+        }
+
+        self.pending.push_back(pending);
+        if !self.is_over_limits(settings, now) {
+            return None;
+        }
+
+        if settings.catch_up {
+            self.start_catch_up();
+        } else {
+            while self.is_over_limits(settings, now) {
+                // Never drop priority messages, or the message that was just queued.
+                let oldest = self.pending.iter().position(|p| !p.request.priority);
+                let Some(index) = oldest.filter(|index| *index != self.pending.len() - 1) else {
+                    break;
+                };
+
+                if let Some(dropped) = self.pending.remove(index) {
+                    self.pending_counts.release(dropped.author_id);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Replaces every waiting message with the names of their authors.
+    fn start_catch_up(&mut self) {
+        for pending in std::mem::take(&mut self.pending) {
+            if pending.request.priority {
+                self.pending.push_back(pending);
+                continue;
+            }
+
+            self.pending_counts.release(pending.author_id);
+            match &mut self.catch_up {
+                Some(catch_up) => {
+                    catch_up.add_name(pending.author_name);
+                    catch_up.template = pending.request;
+                }
+                None => {
+                    self.catch_up = Some(CatchUp {
+                        names: vec![pending.author_name],
+                        template: pending.request,
+                    });
+                }
+            }
+        }
+    }
+
+    fn dispatch(&mut self, author_id: Option<UserId>, duration: Duration, now: Instant) {
+        self.playing_until = self.playing_until.max(now) + duration;
+        self.playing.push_back(PlayingTTS {
+            author_id,
+            until: self.playing_until,
+        });
+    }
+
+    /// Releases the pending counts of the messages estimated to have finished playing.
+    fn release_finished(&mut self, now: Instant) {
+        while let Some(playing) = self.playing.front()
+            && playing.until <= now
+        {
+            if let Some(author_id) = playing.author_id {
+                self.pending_counts.release(author_id);
+            }
+
+            self.playing.pop_front();
+        }
+    }
+
+    /// Returns when [`Self::pop_ready`] should next be called, if there is anything to do.
+    pub fn next_dispatch(&self) -> Option<Instant> {
+        let finished_at = self.playing.front().map(|playing| playing.until);
+        if self.pending.is_empty() && self.catch_up.is_none() {
+            return finished_at;
+        }
+
+        let dispatch_at = self.playing_until.checked_sub(DISPATCH_LOOKAHEAD);
+        let dispatch_at = dispatch_at.unwrap_or(self.playing_until);
+        Some(finished_at.map_or(dispatch_at, |finished_at| finished_at.min(dispatch_at)))
+    }
+
+    /// Takes the next message to send to tts-service, if the current playback is nearly finished.
+    pub fn pop_ready(&mut self) -> Option<GetTTS> {
+        self.pop_ready_at(Instant::now())
+    }
+
+    fn pop_ready_at(&mut self, now: Instant) -> Option<GetTTS> {
+        self.release_finished(now);
+        if now + DISPATCH_LOOKAHEAD < self.playing_until {
+            return None;
+        }
+
+        let front_is_priority = self.pending.front().is_some_and(|p| p.request.priority);
+        if !front_is_priority && let Some(catch_up) = self.catch_up.take() {
+            // The skipped messages are older than anything still waiting, so go first.
+            let request = catch_up.into_summary();
+            self.dispatch(None, estimate_duration(&request), now);
+            return Some(request);
+        }
+
+        let pending = self.pop_next_pending()?;
+        self.dispatch(Some(pending.author_id), pending.duration, now);
+        Some(pending.request)
+    }
+
+    fn pop_next_pending(&mut self) -> Option<PendingTTS> {
+        let index = if self.round_robin && !self.pending.front()?.request.priority {
+            // Serve whoever has waited the longest since they were last read.
+            let (index, _) = self
+                .pending
+                .iter()
+                .enumerate()
+                .min_by_key(|(index, pending)| {
+                    let last_served = self.last_served.get(&pending.author_id);
+                    (last_served.copied().unwrap_or(0), *index)
+                })?;
+
+            index
+        } else {
+            0
+        };
+
+        let pending = self.pending.remove(index)?;
+
+        self.dispatch_count += 1;
+        self.last_served
+            .insert(pending.author_id, self.dispatch_count);
+        if self.pending.is_empty() {
+            self.last_served.clear();
+        }
+
+        Some(pending)
+    }
+
+    /// Skips the estimated playback of the current message, as tts-service has been told to stop it.
+    pub fn interrupt(&mut self) {
+        let now = Instant::now();
+        let Some(skipped) = self.playing.pop_front() else {
+            self.playing_until = now;
+            return;
+        };
+
+        if let Some(author_id) = skipped.author_id {
+            self.pending_counts.release(author_id);
+        }
+
+        let remaining = skipped.until.saturating_duration_since(now);
+        for playing in &mut self.playing {
+            playing.until = playing.until.checked_sub(remaining).unwrap_or(now).max(now);
+        }
+
+        self.playing_until = self.playing.back().map_or(now, |playing| playing.until);
+    }
+
+    /// Forgets everything sent to tts-service, as it has cleared its queue or been replaced.
+    pub fn forget_playing(&mut self) {
+        for playing in std::mem::take(&mut self.playing) {
+            if let Some(author_id) = playing.author_id {
+                self.pending_counts.release(author_id);
+            }
+        }
+
+        self.playing_until = Instant::now();
+    }
+
+    pub fn clear(&mut self) {
+        self.pending_counts.clear();
+        self.pending.clear();
+        self.playing.clear();
+        self.catch_up = None;
+        self.playing_until = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, num::NonZeroU16, time::Duration};
+
+    use poise::serenity_prelude::UserId;
+    use tokio::time::Instant;
+
+    use super::{
+        CatchUp, GetTTS, PendingCounts, QueueSettings, QueuedTTS, TTSQueue, estimate_duration,
+    };
+    use crate::structs::TTSMode;
+
+    const ALICE: UserId = UserId::new(1);
+    const BOB: UserId = UserId::new(2);
+
+    fn request(text: &str, priority: bool) -> GetTTS {
+        GetTTS {
+            text: String::from(text),
+            mode: TTSMode::gTTS,
+            voice: Cow::Borrowed("en"),
+            speaking_rate: None,
+            max_length: None,
+            preferred_format: None,
+            translation_lang: None,
+            priority,
+        }
+    }
+
+    fn queued(author_id: UserId, text: &str, settings: QueueSettings) -> QueuedTTS {
+        let author_name = if author_id == ALICE { "alice" } else { "bob" };
+        QueuedTTS {
+            author_id,
+            author_name: String::from(author_name),
+            settings,
+            request: request(text, false),
+        }
+    }
+
+    fn priority(text: &str, settings: QueueSettings) -> QueuedTTS {
+        QueuedTTS {
+            request: request(text, true),
+            ..queued(ALICE, text, settings)
+        }
+    }
+
+    fn max_messages(max: u16) -> QueueSettings {
+        QueueSettings {
+            max_messages: NonZeroU16::new(max),
+            ..QueueSettings::default()
+        }
+    }
+
+    /// Sends a message that takes a few seconds to read, so the next messages are held back.
+    fn start_reading(queue: &mut TTSQueue, settings: QueueSettings, now: Instant) {
+        let reading = queued(
+            ALICE,
+            "a message that takes a few seconds to read",
+            settings,
+        );
+        assert!(queue.push_at(reading, now).is_some());
+    }
+
+    /// Pops everything, pretending the playback of each message has finished.
+    fn drain(queue: &mut TTSQueue, now: Instant) -> Vec<String> {
+        let mut texts = Vec::new();
+        let mut now = now.max(queue.playing_until);
+        while let Some(request) = queue.pop_ready_at(now) {
+            texts.push(request.text);
+            now = queue.playing_until;
+        }
+
+        texts
+    }
+
+    #[test]
+    fn sends_immediately_without_limits() {
+        let counts = PendingCounts::default();
+        let mut queue = TTSQueue::new(counts.clone());
+        let now = Instant::now();
+
+        assert!(counts.try_reserve(ALICE, NonZeroU16::new(1)));
+        let sent = queue.push_at(queued(ALICE, "hello", QueueSettings::default()), now);
+        assert_eq!(sent.map(|r| r.text).as_deref(), Some("hello"));
+
+        // The message is still counted until it is estimated to have been read.
+        assert!(!counts.try_reserve(ALICE, NonZeroU16::new(1)));
+        assert!(queue.pop_ready_at(now + Duration::from_mins(1)).is_none());
+        assert!(counts.try_reserve(ALICE, NonZeroU16::new(1)));
+    }
+
+    #[test]
+    fn priority_jumps_queue_without_limits() {
+        let mut queue = TTSQueue::new(PendingCounts::default());
+        let settings = QueueSettings::default();
+        let now = Instant::now();
+
+        start_reading(&mut queue, settings, now);
+        assert!(queue.push_at(queued(BOB, "two", settings), now).is_none());
+        assert!(
+            queue
+                .push_at(priority("announcement", settings), now)
+                .is_none()
+        );
+
+        assert_eq!(drain(&mut queue, now), ["announcement", "two"]);
+    }
+
+    #[test]
+    fn faster_speaking_rates_are_shorter() {
+        let at_rate = |speaking_rate| GetTTS {
+            mode: TTSMode::Polly,
+            speaking_rate: Some(speaking_rate),
+            ..request("a message that takes a while to read", false)
+        };
+
+        let normal = estimate_duration(&at_rate(100.0));
+        let doubled = estimate_duration(&at_rate(200.0));
+        assert!(doubled.abs_diff(normal / 2) < Duration::from_millis(1));
+    }
+
+    #[test]
+    fn drops_oldest_but_keeps_newest_and_priority() {
+        let mut queue = TTSQueue::new(PendingCounts::default());
+        let settings = max_messages(2);
+        let now = Instant::now();
+
+        start_reading(&mut queue, settings, now);
+        for text in ["one", "two", "three"] {
+            assert!(queue.push_at(queued(ALICE, text, settings), now).is_none());
+        }
+
+        queue.push_at(priority("announcement", settings), now);
+        queue.push_at(queued(BOB, "four", settings), now);
+
+        assert_eq!(drain(&mut queue, now), ["announcement", "four"]);
+    }
+
+    #[test]
+    fn catch_up_summary_wording() {
+        let summary = |names: &[&str]| {
+            let catch_up = CatchUp {
+                names: names.iter().copied().map(String::from).collect(),
+                template: request("", false),
+            };
+
+            catch_up.into_summary().text
+        };
+
+        assert_eq!(summary(&["alice"]), "alice said things");
+        assert_eq!(summary(&["alice", "bob"]), "alice and bob said things");
+        assert_eq!(
+            summary(&["alice", "bob", "carol"]),
+            "alice, bob and carol said things"
+        );
+        assert_eq!(
+            summary(&["alice", "bob", "carol", "dave", "erin"]),
+            "alice, bob and 3 others said things"
+        );
+    }
+
+    #[test]
+    fn catch_up_ends_under_limits() {
+        let mut queue = TTSQueue::new(PendingCounts::default());
+        let settings = QueueSettings {
+            catch_up: true,
+            ..max_messages(1)
+        };
+
+        let now = Instant::now();
+        start_reading(&mut queue, settings, now);
+        queue.push_at(queued(ALICE, "one", settings), now);
+        queue.push_at(queued(BOB, "two", settings), now);
+
+        let summary = queue.pop_ready_at(queue.playing_until).unwrap();
+        assert_eq!(summary.text, "alice and bob said things");
+
+        // The backlog is back under the limit, so new messages are read normally.
+        let sent = queue.push_at(queued(ALICE, "three", settings), queue.playing_until);
+        assert_eq!(sent.map(|r| r.text).as_deref(), Some("three"));
+    }
+
+    #[test]
+    fn round_robin_alternates_authors() {
+        let mut queue = TTSQueue::new(PendingCounts::default());
+        let settings = QueueSettings {
+            round_robin: true,
+            ..QueueSettings::default()
+        };
+
+        let now = Instant::now();
+        start_reading(&mut queue, settings, now);
+        for text in ["a1", "a2", "a3"] {
+            queue.push_at(queued(ALICE, text, settings), now);
+        }
+
+        queue.push_at(queued(BOB, "b1", settings), now);
+        queue.push_at(queued(BOB, "b2", settings), now);
+
+        assert_eq!(drain(&mut queue, now), ["a1", "b1", "a2", "b2", "a3"]);
+    }
+
+    #[test]
+    fn priority_messages_stay_in_order() {
+        let mut queue = TTSQueue::new(PendingCounts::default());
+        let settings = max_messages(10);
+        let now = Instant::now();
+
+        start_reading(&mut queue, settings, now);
+        queue.push_at(queued(ALICE, "normal", settings), now);
+        queue.push_at(priority("first", settings), now);
+        queue.push_at(priority("second", settings), now);
+
+        assert_eq!(drain(&mut queue, now), ["first", "second", "normal"]);
+    }
+}
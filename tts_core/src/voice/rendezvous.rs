@@ -0,0 +1,147 @@
+use std::num::NonZeroU8;
+
+use sha2::{Digest as _, Sha256};
+
+/// Must stay the same between restarts and versions, or every guild would be reassigned, so a
+/// hash with a fixed specification is used instead of a `Hasher`.
+fn hash(key: u64, service: &str) -> u64 {
+    let digest = Sha256::new()
+        .chain_update(key.to_le_bytes())
+        .chain_update(service.as_bytes())
+        .finalize();
+
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
+
+#[expect(clippy::cast_precision_loss)]
+fn score(key: u64, service: &str, weight: NonZeroU8) -> f64 {
+    // Map the hash to (0, 1), without ever hitting 0 to keep ln finite.
+    let unit = ((hash(key, service) >> 11) as f64 + 0.5) / (1_u64 << 53) as f64;
+    f64::from(weight.get()) / -unit.ln()
+}
+
+/// Selects the index of the service `key` should be assigned to, using weighted rendezvous hashing.
+///
+/// Services are identified by name instead of position, so reordering them has no effect, and
+/// adding, removing, or reweighting a service only moves the keys that have to move.
+pub(crate) fn select<'a>(
+    key: u64,
+    services: impl IntoIterator<Item = (&'a str, NonZeroU8)>,
+) -> Option<usize> {
+    services
+        .into_iter()
+        .map(|(service, weight)| score(key, service, weight))
+        .enumerate()
+        .max_by(|(_, score1), (_, score2)| score1.total_cmp(score2))
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+#[expect(clippy::cast_precision_loss)]
+mod tests {
+    use std::num::NonZeroU8;
+
+    use super::{hash, select};
+
+    const KEY_COUNT: u64 = 20_000;
+
+    fn weight(weight: u8) -> NonZeroU8 {
+        NonZeroU8::new(weight).unwrap()
+    }
+
+    fn assign<'a>(services: &[(&'a str, NonZeroU8)]) -> Vec<&'a str> {
+        (0..KEY_COUNT)
+            .map(|key| key.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .map(|key| services[select(key, services.iter().copied()).unwrap()].0)
+            .collect()
+    }
+
+    fn moved_fraction(before: &[&str], after: &[&str]) -> f64 {
+        let moved = before.iter().zip(after).filter(|(b, a)| b != a).count();
+        moved as f64 / before.len() as f64
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        // Changing these would reassign every guild, see `hash`.
+        assert_eq!(hash(0, "a"), 0x6948_504a_5c8b_fed7);
+        assert_eq!(hash(1_234_567_890, "tts-service"), 0x1406_7b5f_06e3_a951);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(select(1, []), None);
+    }
+
+    #[test]
+    fn reordering_moves_nothing() {
+        let services = [("a", weight(1)), ("b", weight(2)), ("c", weight(1))];
+        let reordered = [("c", weight(1)), ("a", weight(1)), ("b", weight(2))];
+
+        assert_eq!(assign(&services), assign(&reordered));
+    }
+
+    #[test]
+    fn adding_service_moves_its_share() {
+        let before = assign(&[("a", weight(1)), ("b", weight(1)), ("c", weight(1))]);
+        let after = assign(&[
+            ("a", weight(1)),
+            ("b", weight(1)),
+            ("c", weight(1)),
+            ("d", weight(1)),
+        ]);
+
+        // Only keys moving to the new service should be moved.
+        for (before, after) in before.iter().zip(&after) {
+            assert!(before == after || *after == "d");
+        }
+
+        let moved = moved_fraction(&before, &after);
+        assert!((0.2..0.3).contains(&moved), "{moved} of keys moved");
+    }
+
+    #[test]
+    fn removing_service_moves_its_share() {
+        let before = assign(&[
+            ("a", weight(1)),
+            ("b", weight(1)),
+            ("c", weight(1)),
+            ("d", weight(1)),
+        ]);
+        let after = assign(&[("a", weight(1)), ("b", weight(1)), ("d", weight(1))]);
+
+        // Only keys from the removed service should be moved.
+        for (before, after) in before.iter().zip(&after) {
+            assert!(before == after || *before == "c");
+        }
+
+        let moved = moved_fraction(&before, &after);
+        assert!((0.2..0.3).contains(&moved), "{moved} of keys moved");
+    }
+
+    #[test]
+    fn reweighting_moves_its_share() {
+        let before = assign(&[("a", weight(1)), ("b", weight(1))]);
+        let after = assign(&[("a", weight(1)), ("b", weight(3))]);
+
+        // Increasing the weight of b should only move keys to b, from 1/2 to 3/4 of the keys.
+        for (before, after) in before.iter().zip(&after) {
+            assert!(before == after || *after == "b");
+        }
+
+        let moved = moved_fraction(&before, &after);
+        assert!((0.2..0.3).contains(&moved), "{moved} of keys moved");
+    }
+
+    #[test]
+    fn weights_are_respected() {
+        let assigned = assign(&[("a", weight(1)), ("b", weight(3))]);
+        let a_count = assigned.iter().filter(|s| **s == "a").count();
+
+        let a_share = a_count as f64 / assigned.len() as f64;
+        assert!(
+            (0.2..0.3).contains(&a_share),
+            "a was assigned {a_share} of keys"
+        );
+    }
+}
@@ -0,0 +1,640 @@
+use std::{
+    num::NonZeroU8,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU8, Ordering::SeqCst},
+    },
+    time::Duration,
+};
+
+use parking_lot::{RwLock, RwLockReadGuard};
+use rand::Rng as _;
+use tokio::sync::{
+    Mutex as TMutex,
+    mpsc::{self, error::TryRecvError},
+};
+use tokio_tungstenite::tungstenite::{
+    self, Message as RawWSMessage,
+    client::IntoClientRequest as _,
+    error::{ProtocolError, SubProtocolError},
+    http::{HeaderValue, header::SEC_WEBSOCKET_PROTOCOL},
+};
+
+use poise::serenity_prelude::{
+    futures::{self, SinkExt as _, StreamExt as _, stream::SplitSink},
+    small_fixed_array::{FixedArray, TruncatingInto as _},
+};
+
+use super::{
+    InterconnectMessage, RawWSStream, WSEncoding, WSMessageFrame,
+    handshake::{ServiceCapabilities, handshake},
+};
+use crate::structs::{Data, Result, TTSServiceConfig};
+
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+type WSSink = SplitSink<RawWSStream, RawWSMessage>;
+
+struct WSWriter {
+    sink: WSSink,
+    /// Stored alongside the sink, as a reconnect may negotiate a different encoding.
+    encoding: WSEncoding,
+}
+
+/// A single websocket connection to a tts-service.
+///
+/// Incoming messages are handled by a separate task, so health checks never hold the send lock
+/// while waiting for a pong.
+struct PooledConnection {
+    /// Only [`None`] if the tts-service was unreachable on startup.
+    writer: TMutex<Option<WSWriter>>,
+    /// Pongs forwarded by the reader task, which is disconnected once the connection is lost.
+    pongs: TMutex<mpsc::UnboundedReceiver<bytes::Bytes>>,
+}
+
+impl PooledConnection {
+    async fn connect(url: reqwest::Url) -> Result<(Self, ServiceCapabilities)> {
+        let (stream, capabilities) = connect_ws_stream(url).await?;
+        let (sink, pongs) = split_ws_stream(stream);
+        let encoding = capabilities.framing;
+
+        let connection = Self {
+            writer: TMutex::new(Some(WSWriter { sink, encoding })),
+            pongs: TMutex::new(pongs),
+        };
+
+        Ok((connection, capabilities))
+    }
+
+    /// A connection that will fail its first health check, and therefore be reconnected.
+    fn disconnected() -> Self {
+        let (_, pongs) = mpsc::unbounded_channel();
+        Self {
+            writer: TMutex::new(None),
+            pongs: TMutex::new(pongs),
+        }
+    }
+
+    async fn replace(&self, stream: RawWSStream, encoding: WSEncoding) {
+        let (sink, pongs) = split_ws_stream(stream);
+        *self.writer.lock().await = Some(WSWriter { sink, encoding });
+        *self.pongs.lock().await = pongs;
+    }
+}
+
+fn split_ws_stream(stream: RawWSStream) -> (WSSink, mpsc::UnboundedReceiver<bytes::Bytes>) {
+    let (sink, mut stream) = stream.split();
+    let (pong_tx, pong_rx) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        loop {
+            let msg = tokio::select! {
+                // The connection has been replaced or removed.
+                () = pong_tx.closed() => break,
+                msg = stream.next() => msg,
+            };
+
+            match msg {
+                Some(Ok(RawWSMessage::Pong(pong))) => _ = pong_tx.send(pong),
+                Some(Ok(_)) => {}
+                Some(Err(_)) | None => break,
+            }
+        }
+    });
+
+    (sink, pong_rx)
+}
+
+/// A connected tts-service, which voice connections are spread between by weight.
+pub struct TTSService {
+    pub url: reqwest::Url,
+    weight: AtomicU8,
+    connections: FixedArray<PooledConnection, u8>,
+    /// Updated on reconnect, as the tts-service may have been upgraded.
+    capabilities: RwLock<ServiceCapabilities>,
+    healthy: AtomicBool,
+    /// Set once the service has been removed from the config, stopping health checks.
+    removed: AtomicBool,
+}
+
+impl std::fmt::Debug for TTSService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TTSService")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl std::fmt::Display for TTSService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tts-service ({})", self.url)
+    }
+}
+
+impl TTSService {
+    async fn connect(config: &TTSServiceConfig) -> Result<Self> {
+        let connections =
+            (0..config.connections.get()).map(|_| PooledConnection::connect(config.url.clone()));
+
+        let (connections, mut capabilities): (Vec<_>, Vec<_>) =
+            futures::future::try_join_all(connections)
+                .await?
+                .into_iter()
+                .unzip();
+
+        Ok(Self {
+            url: config.url.clone(),
+            weight: AtomicU8::new(config.weight.get()),
+            connections: connections.trunc_into(),
+            capabilities: RwLock::new(capabilities.swap_remove(0)),
+            healthy: AtomicBool::new(true),
+            removed: AtomicBool::new(false),
+        })
+    }
+
+    #[must_use]
+    pub fn capabilities(&self) -> RwLockReadGuard<'_, ServiceCapabilities> {
+        self.capabilities.read()
+    }
+
+    /// Placeholder for a tts-service that could not be reached, kept unhealthy until reconnected.
+    fn disconnected(config: &TTSServiceConfig) -> Self {
+        let connections = (0..config.connections.get()).map(|_| PooledConnection::disconnected());
+
+        Self {
+            url: config.url.clone(),
+            weight: AtomicU8::new(config.weight.get()),
+            connections: connections.collect::<Vec<_>>().trunc_into(),
+            capabilities: RwLock::new(ServiceCapabilities::legacy()),
+            healthy: AtomicBool::new(false),
+            removed: AtomicBool::new(false),
+        }
+    }
+
+    #[must_use]
+    pub fn weight(&self) -> NonZeroU8 {
+        NonZeroU8::new(self.weight.load(SeqCst)).unwrap_or(NonZeroU8::MIN)
+    }
+
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(SeqCst)
+    }
+
+    pub(super) async fn send(&self, frame: &WSMessageFrame<'_>) -> Result<(), tungstenite::Error> {
+        // Each guild always uses the same connection, so its messages cannot be reordered.
+        let index = (frame.guild_id.get() >> 22) % u64::from(self.connections.len());
+        let mut writer = self.connections[index as u8].writer.lock().await;
+        let Some(writer) = &mut *writer else {
+            return Err(tungstenite::Error::AlreadyClosed);
+        };
+
+        let msg = writer.encoding.encode(frame);
+        writer.sink.send(msg).await
+    }
+}
+
+async fn connect_ws_stream(mut url: reqwest::Url) -> Result<(RawWSStream, ServiceCapabilities)> {
+    url.set_path("stream");
+    url.set_scheme("ws").unwrap();
+
+    let mut request = url.clone().into_client_request()?;
+    let protocols = HeaderValue::from_static(WSEncoding::OFFERED_PROTOCOLS);
+    request
+        .headers_mut()
+        .insert(SEC_WEBSOCKET_PROTOCOL, protocols);
+
+    match tokio_tungstenite::connect_async(request).await {
+        Ok((mut stream, response)) => {
+            let capabilities = handshake(&mut stream, WSEncoding::from_response(&response)).await?;
+            Ok((stream, capabilities))
+        }
+        // Older tts-services do not accept any subprotocols, or understand the hello.
+        Err(tungstenite::Error::Protocol(ProtocolError::SecWebSocketSubProtocolError(
+            SubProtocolError::NoSubProtocol,
+        ))) => {
+            let (stream, _) = tokio_tungstenite::connect_async(url).await?;
+            Ok((stream, ServiceCapabilities::legacy()))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Retries connecting until it succeeds, or returns [`None`] once `service` has been removed.
+async fn reconnect_ws_stream(service: &TTSService, index: u8) -> Option<(RawWSStream, WSEncoding)> {
+    let mut interval = tokio::time::interval(Duration::from_millis(500));
+    loop {
+        if service.removed.load(SeqCst) {
+            break None;
+        }
+
+        if let Ok((stream, capabilities)) = connect_ws_stream(service.url.clone()).await {
+            tracing::warn!("Reconnected connection {index} to {service} with {capabilities}");
+
+            let encoding = capabilities.framing;
+            *service.capabilities.write() = capabilities;
+            break Some((stream, encoding));
+        }
+
+        tracing::error!("Failed to reconnect connection {index} to {service}");
+        interval.tick().await;
+    }
+}
+
+type TTSServices = FixedArray<Arc<TTSService>, u8>;
+
+fn into_service_array(services: Vec<Arc<TTSService>>) -> Result<TTSServices> {
+    FixedArray::try_from(services.into_boxed_slice())
+        .map_err(|_| anyhow::anyhow!("Too many TTS services are configured"))
+}
+
+async fn connect_all(configs: &[TTSServiceConfig]) -> Result<Vec<Arc<TTSService>>> {
+    let tasks = configs.iter().map(async |config| {
+        let service = TTSService::connect(config).await?;
+        anyhow::Ok(Arc::new(service))
+    });
+
+    futures::future::try_join_all(tasks).await
+}
+
+/// Connects to every tts-service in the config, keeping any that cannot be reached as unhealthy.
+///
+/// Unhealthy services are retried in the background by [`spawn_health_check`].
+pub async fn connect_tts_services(configs: &[TTSServiceConfig]) -> Result<TTSServices> {
+    let tasks = configs.iter().map(async |config| {
+        let service = TTSService::connect(config).await.unwrap_or_else(|err| {
+            println!(
+                "Failed to connect to {}, retrying in the background: {err:?}",
+                config.url
+            );
+            TTSService::disconnected(config)
+        });
+
+        Arc::new(service)
+    });
+
+    let services = futures::future::join_all(tasks).await;
+    if !services.iter().any(|service| service.is_healthy()) {
+        anyhow::bail!("Could not connect to any TTS services");
+    }
+
+    into_service_array(services)
+}
+
+async fn check_connection_healthy(
+    rng: &mut rand::rngs::SmallRng,
+    connection: &PooledConnection,
+    service: &TTSService,
+    index: u8,
+) -> bool {
+    let mut expected_pong = [0_u8; 64];
+    rng.fill_bytes(&mut expected_pong);
+    let ping = bytes::Bytes::copy_from_slice(&expected_pong);
+
+    // Only the health check reads pongs, so this lock is never contended.
+    let mut pongs = connection.pongs.lock().await;
+    loop {
+        match pongs.try_recv() {
+            // Left over from a previous check that timed out.
+            Ok(_) => {}
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => {
+                tracing::warn!("Connection {index} has been terminated for {service}");
+                return false;
+            }
+        }
+    }
+
+    // The send lock is only held for the ping itself, not while waiting for the pong.
+    let ping_res = match &mut *connection.writer.lock().await {
+        Some(writer) => writer.sink.send(RawWSMessage::Ping(ping.clone())).await,
+        None => Err(tungstenite::Error::AlreadyClosed),
+    };
+
+    if ping_res.is_err() {
+        tracing::warn!("Failed to send ping on connection {index} to {service}");
+        return false;
+    }
+
+    let wait_for_pong = async {
+        while let Some(pong) = pongs.recv().await {
+            if pong == ping {
+                return true;
+            }
+        }
+
+        false
+    };
+
+    if matches!(
+        tokio::time::timeout(Duration::from_secs(1), wait_for_pong).await,
+        Ok(true)
+    ) {
+        tracing::debug!("Health check passed for connection {index} to {service}");
+        true
+    } else {
+        tracing::warn!("Did not recieve pong on connection {index} from {service}");
+        false
+    }
+}
+
+/// Checks every connection to `service`, calling `on_failure` before reconnecting any that failed.
+///
+/// Returns `false` if `service` was removed while reconnecting.
+async fn check_connections(
+    rng: &mut rand::rngs::SmallRng,
+    service: &TTSService,
+    mut on_failure: impl FnMut(),
+) -> bool {
+    for (index, connection) in (0..).zip(service.connections.iter()) {
+        if !check_connection_healthy(rng, connection, service, index).await {
+            on_failure();
+            let Some((stream, encoding)) = reconnect_ws_stream(service, index).await else {
+                return false;
+            };
+
+            connection.replace(stream, encoding).await;
+        }
+    }
+
+    true
+}
+
+/// Checks the connections to `service` every few seconds, reconnecting any that have failed.
+pub fn spawn_health_check(data: Arc<Data>, service: Arc<TTSService>) {
+    tokio::spawn(async move {
+        let mut rng: rand::rngs::SmallRng = rand::make_rng();
+        let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if service.removed.load(SeqCst) {
+                break;
+            }
+
+            // Move voice connections away while reconnecting, as their state will be lost.
+            let on_failure = || set_service_health(&data, &service, false);
+            if !check_connections(&mut rng, &service, on_failure).await {
+                break;
+            }
+
+            // Also restores the health of services marked as unhealthy by a failed send.
+            set_service_health(&data, &service, true);
+        }
+    });
+}
+
+/// Tells every voice task to re-select its tts-service, moving if the selection has changed.
+fn notify_services_changed(data: &Data, reconnected: Option<&Arc<TTSService>>) {
+    for entry in data.voice_connections.lock().values() {
+        let msg = InterconnectMessage::ServicesChanged {
+            reconnected: reconnected.cloned(),
+        };
+
+        entry.interconnect.unbounded_send(msg).ok();
+    }
+}
+
+/// Updates the health of a tts-service, notifying every voice task if it has changed.
+pub fn set_service_health(data: &Data, service: &Arc<TTSService>, healthy: bool) {
+    if service.healthy.swap(healthy, SeqCst) == healthy {
+        return;
+    }
+
+    if healthy {
+        tracing::warn!("{service} is healthy again");
+    } else {
+        tracing::error!("{service} is unhealthy, moving voice connections away");
+    }
+
+    notify_services_changed(data, healthy.then_some(service));
+}
+
+/// The tts-services that were changed by [`reload_tts_services`].
+#[derive(Debug, Default)]
+pub struct ServiceChanges {
+    pub added: Vec<reqwest::Url>,
+    pub removed: Vec<reqwest::Url>,
+    pub reweighted: Vec<reqwest::Url>,
+}
+
+/// Re-reads only the tts-services from `config.toml`, ignoring the rest of the config.
+fn load_tts_service_configs() -> Result<Vec<TTSServiceConfig>> {
+    #[derive(serde::Deserialize)]
+    struct ServicesOnly {
+        #[serde(rename = "TTS-Services")]
+        tts_services: Vec<TTSServiceConfig>,
+    }
+
+    let config: ServicesOnly = toml::from_str(&std::fs::read_to_string("config.toml")?)?;
+    Ok(config.tts_services)
+}
+
+/// Replaces the current tts-services with those in `config.toml`, keeping connections to
+/// unchanged services.
+///
+/// New services are connected to before anything is changed, so a failed reload has no effect.
+/// Voice connections are then re-homed, which only moves the guilds that have to move.
+pub async fn reload_tts_services(data: &Arc<Data>) -> Result<ServiceChanges> {
+    let configs = &load_tts_service_configs()?;
+    if configs.is_empty() {
+        anyhow::bail!("No TTS services are configured");
+    }
+
+    let _guard = data.tts_services_reload_lock.lock().await;
+    let current = data.tts_services.read().clone();
+    // Resized services are reconnected, so are reported as both removed and added.
+    let find_current = |config: &TTSServiceConfig| {
+        current.iter().find(|service| {
+            service.url == config.url && service.connections.len() == config.connections.get()
+        })
+    };
+
+    let new_configs: Vec<_> = configs
+        .iter()
+        .filter(|config| find_current(config).is_none())
+        .cloned()
+        .collect();
+
+    let mut new_services = connect_all(&new_configs).await?.into_iter();
+    let mut changes = ServiceChanges::default();
+
+    let services: Vec<_> = configs
+        .iter()
+        .map(|config| {
+            let Some(service) = find_current(config) else {
+                changes.added.push(config.url.clone());
+                return new_services
+                    .next()
+                    .expect("a service should be connected per new config");
+            };
+
+            Arc::clone(service)
+        })
+        .collect();
+
+    let services = into_service_array(services)?;
+
+    // Only applied once the reload cannot fail, as weights are shared with the current services.
+    for (service, config) in services.iter().zip(configs) {
+        if service.weight.swap(config.weight.get(), SeqCst) != config.weight.get() {
+            changes.reweighted.push(config.url.clone());
+        }
+    }
+
+    for service in &*current {
+        if !services.iter().any(|new| Arc::ptr_eq(new, service)) {
+            service.removed.store(true, SeqCst);
+            changes.removed.push(service.url.clone());
+        }
+    }
+
+    for service in &*services {
+        if changes.added.contains(&service.url) {
+            tracing::warn!("Connected to {service} with {}", *service.capabilities());
+            spawn_health_check(Arc::clone(data), Arc::clone(service));
+        }
+    }
+
+    *data.tts_services.write() = services;
+    notify_services_changed(data, None);
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::Cow,
+        num::NonZeroU8,
+        sync::atomic::Ordering::SeqCst,
+        time::{Duration, Instant},
+    };
+
+    use poise::serenity_prelude::GuildId;
+    use tts_mock_service::{MockConfig, MockService};
+
+    use super::{TTSService, check_connections};
+    use crate::{
+        structs::{TTSMode, TTSServiceConfig},
+        voice::{GetTTS, WSEncoding, WSMessage, WSMessageFrame},
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    async fn connect(mock: &MockService) -> TTSService {
+        let config = TTSServiceConfig {
+            url: mock.url(),
+            weight: NonZeroU8::MIN,
+            connections: NonZeroU8::MIN,
+        };
+
+        TTSService::connect(&config).await.unwrap()
+    }
+
+    /// Waits for the mock to close every connection after [`MockService::disconnect_all`].
+    async fn wait_for_disconnect(mock: &MockService) {
+        let start = Instant::now();
+        while mock.connection_count() != 0 {
+            assert!(start.elapsed() < TIMEOUT, "mock did not disconnect");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    fn queue_tts(text: &str) -> WSMessage<'static> {
+        WSMessage::QueueTTS(GetTTS {
+            text: text.into(),
+            mode: TTSMode::gTTS,
+            voice: Cow::Borrowed(TTSMode::gTTS.default_voice()),
+            speaking_rate: None,
+            max_length: Some(30),
+            preferred_format: None,
+            translation_lang: None,
+            priority: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn negotiates_messagepack() {
+        let mock = MockService::start(MockConfig::default()).await.unwrap();
+        let service = connect(&mock).await;
+
+        let capabilities = service.capabilities().clone();
+        assert_eq!(capabilities.framing, WSEncoding::MessagePack);
+        assert!(capabilities.skip);
+
+        let guild_id = GuildId::new(1 << 22);
+        for inner in [queue_tts("Hello"), WSMessage::Skip, WSMessage::Leave] {
+            service
+                .send(&WSMessageFrame { guild_id, inner })
+                .await
+                .unwrap();
+        }
+
+        let frames = mock.wait_for_frames(3, TIMEOUT).await;
+        let kinds: Vec<_> = frames.iter().map(|frame| frame.kind.as_str()).collect();
+        assert_eq!(kinds, ["QueueTTS", "Skip", "Leave"]);
+        assert!(frames.iter().all(|frame| frame.guild_id == guild_id.get()));
+        assert_eq!(frames[0].payload["text"], "Hello");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_legacy() {
+        let mock = MockService::start(MockConfig::legacy()).await.unwrap();
+        let service = connect(&mock).await;
+
+        let capabilities = service.capabilities().clone();
+        assert_eq!(capabilities.protocol_version, 0);
+        assert_eq!(capabilities.framing, WSEncoding::Json);
+
+        let frame = WSMessageFrame {
+            guild_id: GuildId::new(1),
+            inner: queue_tts("Hello"),
+        };
+
+        service.send(&frame).await.unwrap();
+
+        let frames = mock.wait_for_frames(1, TIMEOUT).await;
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].payload["lang"], "en");
+    }
+
+    #[tokio::test]
+    async fn health_check_reconnects() {
+        let mock = MockService::start(MockConfig::default()).await.unwrap();
+        let service = connect(&mock).await;
+        let mut rng: rand::rngs::SmallRng = rand::make_rng();
+
+        let mut failures = 0;
+        assert!(check_connections(&mut rng, &service, || failures += 1).await);
+        assert_eq!(failures, 0);
+
+        mock.disconnect_all();
+        wait_for_disconnect(&mock).await;
+
+        assert!(check_connections(&mut rng, &service, || failures += 1).await);
+        assert_eq!((failures, mock.connection_count()), (1, 1));
+
+        let frame = WSMessageFrame {
+            guild_id: GuildId::new(1),
+            inner: queue_tts("Reconnected"),
+        };
+
+        service.send(&frame).await.unwrap();
+        let frames = mock.wait_for_frames(1, TIMEOUT).await;
+        assert_eq!(frames[0].payload["text"], "Reconnected");
+    }
+
+    #[tokio::test]
+    async fn removed_service_is_not_reconnected() {
+        let mock = MockService::start(MockConfig::default()).await.unwrap();
+        let service = connect(&mock).await;
+        let mut rng: rand::rngs::SmallRng = rand::make_rng();
+
+        service.removed.store(true, SeqCst);
+        mock.disconnect_all();
+        wait_for_disconnect(&mock).await;
+
+        assert!(!check_connections(&mut rng, &service, || {}).await);
+        assert_eq!(mock.connection_count(), 0);
+    }
+}
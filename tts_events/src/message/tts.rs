@@ -1,16 +1,14 @@
-use std::{
-    borrow::Cow,
-    sync::{Arc, atomic::AtomicU64},
-};
+use std::sync::{Arc, atomic::AtomicU64};
 
 use poise::serenity_prelude as serenity;
 
 use ::serenity::small_fixed_array::FixedString;
 use tts_core::{
+    constants::MAX_MESSAGE_LENGTH,
     database::{GuildRow, UserRow},
     opt_ext::OptionTryUnwrap as _,
     process_msg::{self, MessageContent, TTSMessageKind},
-    structs::{Data, IsPremium, Result, TTSMode},
+    structs::{Data, IsPremium, Result},
     voice,
 };
 
@@ -33,7 +31,7 @@ pub(crate) async fn process_tts_msg(
     };
 
     let is_premium = data.is_premium_simple(&ctx.http, guild_id).await?;
-    let (voice, mode) = {
+    let (voice, mode, author_name) = {
         let is_ephemeral = message
             .flags
             .is_some_and(|f| f.contains(serenity::model::channel::MessageFlags::EPHEMERAL));
@@ -72,7 +70,14 @@ pub(crate) async fn process_tts_msg(
             get_should_announce,
         );
 
-        (voice, mode)
+        let author_name = nickname_row
+            .name
+            .as_deref()
+            .or(member_nick)
+            .or(message.author.global_name.as_deref())
+            .unwrap_or(&message.author.name);
+
+        (voice, mode, String::from(author_name))
     };
 
     // Final check, make sure we aren't sending an empty message or just symbols.
@@ -83,7 +88,7 @@ pub(crate) async fn process_tts_msg(
     // Try to join VC, if we are already in VC we will just get an error back.
     //
     // This also handles autojoining and cases where Voice Client and Voice Connection state have desync'd due to restarts.
-    let voice_tx = {
+    let connection = {
         let author_voice_channel_id = {
             let guild = ctx.cache.guild(guild_id).try_unwrap()?;
             guild
@@ -96,22 +101,24 @@ pub(crate) async fn process_tts_msg(
             serenity: ctx.clone(),
             bot_id: ctx.cache.current_user().id,
             guild_id,
-            channel_id: author_voice_channel_id
+            channel_id: guild_row
+                .autojoin_channel
+                .or(author_voice_channel_id)
                 .map(serenity::ChannelId::get)
                 .map(AtomicU64::new)
                 .map(Arc::new),
         };
 
         match voice::start_connection(data, voice_context).await {
-            voice::StartConnectionResult::Started(tx)
-            | voice::StartConnectionResult::AlreadyIn((tx, _, _)) => tx,
+            voice::StartConnectionResult::Started(entry)
+            | voice::StartConnectionResult::AlreadyIn(entry) => entry,
             voice::StartConnectionResult::CannotJoin | voice::StartConnectionResult::TimedOut => {
                 return Ok(());
             }
         }
     };
 
-    let tx_res = voice_tx.unbounded_send(voice::InterconnectMessage::QueueTTS(voice::GetTTS {
+    let request = voice::GetTTS {
         text: content.text,
         mode,
         voice,
@@ -121,18 +128,30 @@ pub(crate) async fn process_tts_msg(
         translation_lang: guild_row
             .target_lang(IsPremium::from(is_premium))
             .map(FixedString::from_str_trunc),
-    }));
+        priority: false,
+    };
+
+    let max_user_queue = guild_row.max_user_queue(IsPremium::from(is_premium));
+    if !connection
+        .pending_counts
+        .try_reserve(message.author.id, max_user_queue)
+    {
+        // Let the author know they are being skipped, this is fine to fail without permissions.
+        _ = message.react(&ctx.http, '⏳').await;
+        return Ok(());
+    }
+
+    let tx_res = connection
+        .interconnect
+        .unbounded_send(voice::InterconnectMessage::QueueTTS(voice::QueuedTTS {
+            author_id: message.author.id,
+            author_name,
+            settings: guild_row.queue_settings(),
+            request,
+        }));
 
     if tx_res.is_ok() {
-        data.analytics.log(
-            Cow::Borrowed(match mode {
-                TTSMode::gTTS => "gTTS_tts",
-                TTSMode::eSpeak => "eSpeak_tts",
-                TTSMode::gCloud => "gCloud_tts",
-                TTSMode::Polly => "Polly_tts",
-            }),
-            false,
-        );
+        data.analytics.log(mode.analytics_event(), false);
     }
 
     Ok(())
@@ -209,7 +228,7 @@ fn run_checks<'c>(
         serenity::content_safe(&guild, content, options, mentions)
     };
 
-    if content.len() >= 1500 {
+    if content.len() >= MAX_MESSAGE_LENGTH {
         return Ok(None);
     }
 
@@ -247,6 +266,11 @@ fn run_checks<'c>(
             }
         } else if !guild_row.auto_join() {
             return Ok(None); // Bot not in vc and not auto joining
+        } else if let Some(autojoin_channel) = guild_row.autojoin_channel
+            && guild_row.require_voice()
+            && voice_state.and_then(|vs| vs.channel_id) != Some(autojoin_channel)
+        {
+            return Ok(None); // Autojoining a channel the user is not in
         }
 
         // If the user's voice channel is a stage, audience ignore is enabled, and the user is server muted: skip
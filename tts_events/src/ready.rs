@@ -1,4 +1,4 @@
-use std::{fmt::Write, num::NonZeroU16, sync::atomic::Ordering};
+use std::{fmt::Write, num::NonZeroU16, sync::atomic::Ordering, time::Duration};
 
 use aformat::aformat;
 
@@ -8,6 +8,7 @@ use poise::serenity_prelude as serenity;
 use tts_core::{
     constants::FREE_NEUTRAL_COLOUR,
     structs::{Data, Result},
+    voice,
 };
 use tts_tasks::Looper;
 
@@ -107,11 +108,60 @@ fn finalize_startup(ctx: &serenity::Context, data: &Data) {
         tokio::spawn(web_updater.start());
     }
 
+    let catalogue_refresher = tts_tasks::catalogue_refresher::CatalogueRefresher::new(ctx.data());
+    tokio::spawn(catalogue_refresher.start());
+
     // Tell glibc to let go of the memory it's holding onto.
     // We are very unlikely to reach the peak of memory allocation that was just hit.
     clear_allocator_cache();
 }
 
+/// How long to wait for the shard's guilds to arrive before restoring voice connections.
+const RESTORE_START_DELAY: Duration = Duration::from_secs(10);
+
+fn has_human_listeners(
+    cache: &serenity::Cache,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) -> bool {
+    let Some(guild) = cache.guild(guild_id) else {
+        return false;
+    };
+
+    guild.voice_states.iter().any(|vs| {
+        vs.channel_id == Some(channel_id)
+            && guild
+                .members
+                .get(&vs.user_id)
+                .is_some_and(|member| !member.user.bot())
+    })
+}
+
+/// Rejoins the voice channels this shard was connected to before the bot restarted.
+async fn restore_voice_connections(ctx: serenity::Context) -> Result<()> {
+    let data = ctx.data_ref::<Data>();
+    let shard_count = ctx.cache.shard_count();
+    let saved = voice::fetch_saved_connections(data, ctx.shard_id, shard_count).await?;
+    if saved.is_empty() {
+        return Ok(());
+    }
+
+    tokio::time::sleep(RESTORE_START_DELAY).await;
+
+    let mut interval = tokio::time::interval(voice::REJOIN_INTERVAL);
+    for (guild_id, channel_id) in saved {
+        if !has_human_listeners(&ctx.cache, guild_id, channel_id) {
+            voice::forget_connection(data, guild_id).await;
+            continue;
+        }
+
+        interval.tick().await;
+        voice::rejoin(&ctx, guild_id, channel_id, "saved voice channel");
+    }
+
+    Ok(())
+}
+
 pub async fn handle(ctx: &serenity::Context, data_about_bot: &serenity::Ready) -> Result<()> {
     let data = ctx.data_ref::<Data>();
 
@@ -130,6 +180,19 @@ pub async fn handle(ctx: &serenity::Context, data_about_bot: &serenity::Ready) -
         .bot_mention
         .get_or_init(|| regex::Regex::new(&aformat!("^<@!?{}>$", data_about_bot.user.id)).unwrap());
 
+    // Ready is sent again on reconnects, but these tasks should only be spawned once per shard.
+    if data.ready_shards.lock().insert(ctx.shard_id) {
+        let restore_ctx = ctx.clone();
+        tokio::spawn(async move {
+            if let Err(err) = restore_voice_connections(restore_ctx).await {
+                tracing::error!("Failed to restore voice connections: {err:?}");
+            }
+        });
+
+        let home_channels = tts_tasks::home_channels::HomeChannelSupervisor::new(ctx.clone());
+        tokio::spawn(home_channels.start());
+    }
+
     if is_last_shard && !data.fully_started.swap(true, Ordering::SeqCst) {
         finalize_startup(ctx, data);
     } else if data.fully_started.load(Ordering::SeqCst) {
@@ -174,6 +174,18 @@ async fn run_(config: &mut toml::Table, transaction: &mut Transaction<'_>) -> Re
             ON DELETE CASCADE
         );
 
+        CREATE TABLE IF NOT EXISTS voice_connections (
+            guild_id      bigint       PRIMARY KEY,
+            channel_id    bigint       NOT NULL,
+            joined_at     timestamptz  NOT NULL DEFAULT now()
+        );
+
+        CREATE TABLE IF NOT EXISTS catalogue_snapshot (
+            id            bool         PRIMARY KEY DEFAULT True CHECK (id),
+            catalogues    jsonb        NOT NULL,
+            saved_at      timestamptz  NOT NULL DEFAULT now()
+        );
+
         ALTER TABLE userinfo
             ADD COLUMN IF NOT EXISTS voice_mode          TTSMode,
             ADD COLUMN IF NOT EXISTS premium_voice_mode  TTSMode,
@@ -189,7 +201,15 @@ async fn run_(config: &mut toml::Table, transaction: &mut Transaction<'_>) -> Re
             ADD COLUMN IF NOT EXISTS required_role    bigint,
             ADD COLUMN IF NOT EXISTS required_prefix  varchar(6),
             ADD COLUMN IF NOT EXISTS text_in_voice    bool       DEFAULT True,
-            ADD COLUMN IF NOT EXISTS skip_emoji       bool       DEFAULT False;
+            ADD COLUMN IF NOT EXISTS skip_emoji       bool       DEFAULT False,
+            ADD COLUMN IF NOT EXISTS home_channel     bigint,
+            ADD COLUMN IF NOT EXISTS autojoin_channel bigint,
+            ADD COLUMN IF NOT EXISTS max_queue_length  smallint   DEFAULT 0,
+            ADD COLUMN IF NOT EXISTS max_queue_seconds smallint   DEFAULT 0,
+            ADD COLUMN IF NOT EXISTS queue_catch_up    bool       DEFAULT False,
+            ADD COLUMN IF NOT EXISTS queue_round_robin bool       DEFAULT False,
+            ADD COLUMN IF NOT EXISTS max_user_queue    smallint   DEFAULT 0,
+            ADD COLUMN IF NOT EXISTS announce_role     bigint;
         ALTER TABLE user_voice
             ADD COLUMN IF NOT EXISTS speaking_rate real;
 
@@ -239,10 +259,29 @@ pub async fn load_db_and_conf() -> Result<(sqlx::PgPool, Config)> {
     let pool = pool_config.connect_with(pool_options).await?;
     run(&mut config_toml, &pool).await?;
 
-    let config: Config = config_toml.try_into()?;
+    let mut config: Config = config_toml.try_into()?;
     if config.tts_services.is_empty() {
         return Err(anyhow::anyhow!("No TTS services are configured"));
     }
 
+    TTSMode::register_custom(std::mem::take(&mut config.custom_modes))?;
+    add_custom_modes(&pool).await?;
+
     Ok((pool, config))
 }
+
+/// Adds the custom modes to the `TTSMode` type, which cannot be done in the migration transaction.
+async fn add_custom_modes(pool: &sqlx::PgPool) -> Result<()> {
+    for mode in TTSMode::all().filter(|mode| matches!(mode, TTSMode::Custom(_))) {
+        // The name has already been checked to only contain letters, digits and underscores.
+        let query = format!(
+            "ALTER TYPE TTSMode ADD VALUE IF NOT EXISTS '{}'",
+            mode.db_name()
+        );
+        sqlx::query(sqlx::AssertSqlSafe(&*query))
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(())
+}
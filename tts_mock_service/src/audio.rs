@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+const SAMPLE_RATE: u32 = 24000;
+const TIME_PER_CHAR: Duration = Duration::from_millis(60);
+const MIN_DURATION: Duration = Duration::from_millis(250);
+const MAX_DURATION: Duration = Duration::from_secs(30);
+
+/// What the generated audio sounds like.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioKind {
+    Silence,
+    /// A quiet tone, so playback can be heard when testing by hand.
+    Sine {
+        frequency: f32,
+    },
+}
+
+impl Default for AudioKind {
+    fn default() -> Self {
+        Self::Sine { frequency: 440.0 }
+    }
+}
+
+/// Roughly how long speaking `text` would take, so longer messages play for longer.
+pub(crate) fn duration_for(text: &str) -> Duration {
+    (TIME_PER_CHAR * text.chars().count() as u32).clamp(MIN_DURATION, MAX_DURATION)
+}
+
+/// A 16-bit mono WAV file of `duration`.
+pub(crate) fn generate(kind: AudioKind, duration: Duration) -> Vec<u8> {
+    let sample_count = (duration.as_secs_f64() * f64::from(SAMPLE_RATE)) as u32;
+    let data_length = sample_count * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_length as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_length).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16_u32.to_le_bytes());
+    wav.extend_from_slice(&1_u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1_u16.to_le_bytes()); // Mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2_u16.to_le_bytes());
+    wav.extend_from_slice(&16_u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_length.to_le_bytes());
+
+    let frequency = match kind {
+        AudioKind::Silence => None,
+        AudioKind::Sine { frequency } => Some(frequency),
+    };
+
+    for i in 0..sample_count {
+        let sample = frequency.map_or(0, |frequency| {
+            let time = f64::from(i) / f64::from(SAMPLE_RATE);
+            let amplitude = (time * f64::from(frequency) * std::f64::consts::TAU).sin();
+            (amplitude * f64::from(i16::MAX / 4)) as i16
+        });
+
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+
+    wav
+}
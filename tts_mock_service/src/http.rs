@@ -0,0 +1,167 @@
+use tokio::io::{AsyncBufRead, AsyncBufReadExt as _, AsyncWrite, AsyncWriteExt as _};
+
+use crate::{MockConfig, audio, voices};
+
+const MAX_HEAD_LENGTH: usize = 16 * 1024;
+
+/// The head of a HTTP/1.1 request, as nothing the bot sends has a body.
+pub(crate) struct Request {
+    method: String,
+    target: String,
+    /// Names are lowercased.
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    pub(crate) async fn read(stream: &mut (impl AsyncBufRead + Unpin)) -> anyhow::Result<Self> {
+        let mut head_length = 0;
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            head_length += stream.read_line(&mut line).await?;
+            anyhow::ensure!(head_length <= MAX_HEAD_LENGTH, "Request head is too long");
+
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                break;
+            }
+
+            lines.push(line.to_owned());
+        }
+
+        let mut lines = lines.into_iter();
+        let request_line = lines
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Empty request"))?;
+        let mut request_line = request_line.split(' ');
+        let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+            anyhow::bail!("Malformed request line");
+        };
+
+        let headers = lines.filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_ascii_lowercase(), value.trim().to_owned()))
+        });
+
+        Ok(Self {
+            method: method.to_owned(),
+            target: target.to_owned(),
+            headers: headers.collect(),
+        })
+    }
+
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        let mut headers = self.headers.iter();
+        headers
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn is_websocket_upgrade(&self) -> bool {
+        self.header("upgrade")
+            .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+    }
+
+    pub(crate) fn path(&self) -> &str {
+        self.target
+            .split_once('?')
+            .map_or(&self.target, |(path, _)| path)
+    }
+
+    fn query(&self, key: &str) -> Option<String> {
+        let url = reqwest::Url::parse(&format!("http://mock{}", self.target)).ok()?;
+        let mut pairs = url.query_pairs();
+        pairs
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+pub(crate) struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json(status: &'static str, body: &serde_json::Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: serde_json::to_vec(body).unwrap(),
+        }
+    }
+
+    /// An error in the format of tts-service, which the bot shows or ignores by `code`.
+    fn error(status: &'static str, code: u8, display: &str) -> Self {
+        Self::json(
+            status,
+            &serde_json::json!({"code": code, "display": display}),
+        )
+    }
+
+    pub(crate) fn not_found() -> Self {
+        Self::error("404 Not Found", 0, "Not Found")
+    }
+
+    pub(crate) async fn write(&self, stream: &mut (impl AsyncWrite + Unpin)) -> anyhow::Result<()> {
+        let head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            self.content_type,
+            self.body.len()
+        );
+
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&self.body).await?;
+        stream.shutdown().await?;
+        Ok(())
+    }
+}
+
+pub(crate) fn route(request: &Request, config: &MockConfig) -> Response {
+    if request.method != "GET" {
+        return Response::error("405 Method Not Allowed", 0, "Method Not Allowed");
+    }
+
+    match request.path() {
+        "/voices" => match request
+            .query("mode")
+            .and_then(|mode| voices::for_mode(&mode, config))
+        {
+            Some(voices) => Response::json("200 OK", &voices),
+            None => Response::error("400 Bad Request", 0, "Unknown mode"),
+        },
+        "/translation_languages" => Response::json("200 OK", &voices::translation_languages()),
+        "/tts" => tts(request, config),
+        _ => Response::not_found(),
+    }
+}
+
+fn tts(request: &Request, config: &MockConfig) -> Response {
+    let (Some(text), Some(mode), Some(voice)) = (
+        request.query("text"),
+        request.query("mode"),
+        request.query("lang"),
+    ) else {
+        return Response::error("400 Bad Request", 0, "Missing text, mode, or lang");
+    };
+
+    if !voices::codes(&mode, config).is_some_and(|codes| codes.contains(&voice)) {
+        return Response::error("400 Bad Request", 1, "Unknown voice");
+    }
+
+    let duration = audio::duration_for(&text);
+    let max_length = request
+        .query("max_length")
+        .and_then(|max| max.parse::<f32>().ok());
+    if max_length.is_some_and(|max_length| duration.as_secs_f32() > max_length) {
+        return Response::error("400 Bad Request", 2, "Audio is too long");
+    }
+
+    Response {
+        status: "200 OK",
+        content_type: "audio/wav",
+        body: audio::generate(config.audio, duration),
+    }
+}
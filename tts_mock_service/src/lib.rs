@@ -0,0 +1,244 @@
+//! A stand-in for tts-service, implementing the same HTTP and websocket contract with canned
+//! voices and generated audio, for local development and integration tests.
+//!
+//! Every `WSMessageFrame` received is recorded, see [`MockService::frames`].
+use std::{
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering::SeqCst},
+    },
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use tokio::{
+    io::BufReader,
+    net::{TcpListener, TcpStream},
+    sync::{Notify, watch},
+    task::JoinHandle,
+};
+
+mod audio;
+mod http;
+mod voices;
+mod ws;
+
+pub use audio::AudioKind;
+
+/// How the mock should behave, which defaults to the newest tts-service.
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct MockConfig {
+    /// Agree to a subprotocol and reply to the hello, instead of acting like an older tts-service.
+    pub handshake: bool,
+    /// Agree to `tts-service.msgpack` if offered, instead of always picking JSON.
+    pub msgpack: bool,
+    /// Reported in the hello reply, along with `pause`.
+    pub skip: bool,
+    pub pause: bool,
+    /// Custom modes to report and serve voices for, as `(mode_name, [voice])`.
+    pub custom_modes: Vec<(String, Vec<String>)>,
+    pub audio: AudioKind,
+    /// Prints every recorded frame, for running the binary by hand.
+    pub log_frames: bool,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            handshake: true,
+            msgpack: true,
+            skip: true,
+            pause: true,
+            custom_modes: Vec::new(),
+            audio: AudioKind::default(),
+            log_frames: false,
+        }
+    }
+}
+
+impl MockConfig {
+    /// Acts like a tts-service from before the hello, which only understands JSON.
+    #[must_use]
+    pub fn legacy() -> Self {
+        Self {
+            handshake: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// A frame sent by the bot, decoded from either JSON or `MessagePack`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedFrame {
+    pub guild_id: u64,
+    /// The `WSMessage` variant, such as `QueueTTS` or `Leave`.
+    pub kind: String,
+    /// The contents of the variant, or [`serde_json::Value::Null`] for unit variants.
+    pub payload: serde_json::Value,
+}
+
+impl RecordedFrame {
+    fn from_value(value: &serde_json::Value) -> Option<Self> {
+        let guild_id = value.get("guild_id")?.as_u64()?;
+        let (kind, payload) = match value.get("inner")? {
+            serde_json::Value::String(kind) => (kind.clone(), serde_json::Value::Null),
+            serde_json::Value::Object(inner) if inner.len() == 1 => {
+                let (kind, payload) = inner.iter().next()?;
+                (kind.clone(), payload.clone())
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            guild_id,
+            kind,
+            payload,
+        })
+    }
+}
+
+struct State {
+    config: MockConfig,
+    frames: Mutex<Vec<RecordedFrame>>,
+    frame_recorded: Notify,
+    connections: AtomicUsize,
+    /// Bumped to close every open websocket connection.
+    disconnect: watch::Sender<u64>,
+}
+
+impl State {
+    fn record(&self, frame: RecordedFrame) {
+        if self.config.log_frames {
+            println!("Received {frame:?}");
+        }
+
+        self.frames.lock().push(frame);
+        self.frame_recorded.notify_waiters();
+    }
+}
+
+/// A running mock tts-service, which stops when dropped.
+pub struct MockService {
+    addr: SocketAddr,
+    state: Arc<State>,
+    listener_task: JoinHandle<()>,
+}
+
+impl MockService {
+    /// Starts the mock on a random local port.
+    pub async fn start(config: MockConfig) -> std::io::Result<Self> {
+        Self::bind(SocketAddr::from(([127, 0, 0, 1], 0)), config).await
+    }
+
+    pub async fn bind(addr: SocketAddr, config: MockConfig) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let state = Arc::new(State {
+            config,
+            frames: Mutex::new(Vec::new()),
+            frame_recorded: Notify::new(),
+            connections: AtomicUsize::new(0),
+            disconnect: watch::Sender::new(0),
+        });
+
+        Ok(Self {
+            addr: listener.local_addr()?,
+            listener_task: tokio::spawn(accept_loop(listener, Arc::clone(&state))),
+            state,
+        })
+    }
+
+    /// The URL to put in a `[[TTS-Services]]` entry of `config.toml`.
+    #[must_use]
+    pub fn url(&self) -> reqwest::Url {
+        reqwest::Url::parse(&format!("http://{}", self.addr)).unwrap()
+    }
+
+    #[must_use]
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Every frame recorded so far, in the order they were received.
+    #[must_use]
+    pub fn frames(&self) -> Vec<RecordedFrame> {
+        self.state.frames.lock().clone()
+    }
+
+    pub fn clear_frames(&self) {
+        self.state.frames.lock().clear();
+    }
+
+    /// Waits until at least `count` frames have been recorded, or `timeout` has passed.
+    pub async fn wait_for_frames(&self, count: usize, timeout: Duration) -> Vec<RecordedFrame> {
+        let wait = async {
+            loop {
+                // Created before checking, so a frame recorded in between is not missed.
+                let recorded = self.state.frame_recorded.notified();
+                if self.state.frames.lock().len() >= count {
+                    break;
+                }
+
+                recorded.await;
+            }
+        };
+
+        _ = tokio::time::timeout(timeout, wait).await;
+        self.frames()
+    }
+
+    /// The number of websocket connections currently open.
+    #[must_use]
+    pub fn connection_count(&self) -> usize {
+        self.state.connections.load(SeqCst)
+    }
+
+    /// Closes every open websocket connection, to test reconnecting.
+    pub fn disconnect_all(&self) {
+        self.state
+            .disconnect
+            .send_modify(|generation| *generation += 1);
+    }
+}
+
+impl Drop for MockService {
+    fn drop(&mut self) {
+        self.listener_task.abort();
+        self.disconnect_all();
+    }
+}
+
+async fn accept_loop(listener: TcpListener, state: Arc<State>) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, &state).await
+                && state.config.log_frames
+            {
+                println!("Connection failed: {err:?}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, state: &State) -> anyhow::Result<()> {
+    let mut stream = BufReader::new(stream);
+    let request = http::Request::read(&mut stream).await?;
+
+    if request.is_websocket_upgrade() {
+        if request.path() == "/stream" {
+            return ws::handle(stream, &request, state).await;
+        }
+
+        return http::Response::not_found().write(&mut stream).await;
+    }
+
+    http::route(&request, &state.config)
+        .write(&mut stream)
+        .await
+}
@@ -0,0 +1,33 @@
+use std::net::SocketAddr;
+
+use tts_mock_service::{AudioKind, MockConfig, MockService};
+
+const USAGE: &str = "Usage: tts_mock_service [address] [--legacy] [--json] [--silence]";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut addr = SocketAddr::from(([127, 0, 0, 1], 20310));
+    let mut config = MockConfig {
+        log_frames: true,
+        ..MockConfig::default()
+    };
+
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--legacy" => config.handshake = false,
+            "--json" => config.msgpack = false,
+            "--silence" => config.audio = AudioKind::Silence,
+            "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ => addr = arg.parse().map_err(|_| anyhow::anyhow!("{USAGE}"))?,
+        }
+    }
+
+    let service = MockService::bind(addr, config).await?;
+    println!("Mock tts-service listening on {}", service.url());
+
+    tokio::signal::ctrl_c().await?;
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use serde_json::{Value, json};
+
+use crate::MockConfig;
+
+// Each includes the default voice of its mode, so the bot works without any settings.
+
+/// `(code, name)`
+const GTTS_VOICES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("es", "Spanish"),
+];
+
+const ESPEAK_VOICES: &[&str] = &["en1", "en2", "fr1", "de1"];
+
+/// `(id, language_code, language_name, gender)`
+const POLLY_VOICES: &[(&str, &str, &str, &str)] = &[
+    ("Brian", "en-GB", "British English", "Male"),
+    ("Amy", "en-GB", "British English", "Female"),
+    ("Joanna", "en-US", "US English", "Female"),
+    ("Mathieu", "fr-FR", "French", "Male"),
+];
+
+/// `(language_code, variant, ssml_gender)`
+const GCLOUD_VOICES: &[(&str, &str, &str)] = &[
+    ("en-US", "A", "MALE"),
+    ("en-US", "C", "FEMALE"),
+    ("en-GB", "A", "FEMALE"),
+    ("fr-FR", "A", "FEMALE"),
+];
+
+/// (code, name), uppercase as tts-service forwards them from the translation API.
+const TRANSLATION_LANGUAGES: &[(&str, &str)] = &[
+    ("EN", "English"),
+    ("FR", "French"),
+    ("DE", "German"),
+    ("ES", "Spanish"),
+];
+
+/// The modes reported in the hello, built in first.
+pub(crate) fn modes(config: &MockConfig) -> Vec<String> {
+    let built_in = ["gTTS", "eSpeak", "gCloud", "Polly"].map(String::from);
+    let custom = config.custom_modes.iter().map(|(name, _)| name.clone());
+    built_in.into_iter().chain(custom).collect()
+}
+
+/// The response to `/voices?mode={mode}&raw=true`, or [`None`] for an unknown mode.
+pub(crate) fn for_mode(mode: &str, config: &MockConfig) -> Option<Value> {
+    let voices = match mode {
+        "gTTS" => Value::Object(
+            GTTS_VOICES
+                .iter()
+                .map(|&(code, name)| (code.to_owned(), json!(name)))
+                .collect(),
+        ),
+        "eSpeak" => json!(ESPEAK_VOICES),
+        "Polly" => POLLY_VOICES
+            .iter()
+            .map(|&(id, language_code, language_name, gender)| {
+                json!({
+                    "additional_language_codes": null,
+                    "language_code": language_code,
+                    "language_name": language_name,
+                    "gender": gender,
+                    "name": id,
+                    "id": id,
+                })
+            })
+            .collect(),
+        "gCloud" => GCLOUD_VOICES
+            .iter()
+            .map(|&(language_code, variant, gender)| {
+                json!({
+                    "name": format!("{language_code}-Standard-{variant}"),
+                    "ssmlGender": gender,
+                    "languageCodes": [language_code],
+                })
+            })
+            .collect(),
+        _ => json!(custom_voices(mode, config)?),
+    };
+
+    Some(voices)
+}
+
+/// The voices accepted as `lang` by `/tts` for `mode`, or [`None`] for an unknown mode.
+pub(crate) fn codes(mode: &str, config: &MockConfig) -> Option<Vec<String>> {
+    let codes = match mode {
+        "gTTS" => GTTS_VOICES
+            .iter()
+            .map(|(code, _)| (*code).to_owned())
+            .collect(),
+        "eSpeak" => ESPEAK_VOICES
+            .iter()
+            .map(|voice| (*voice).to_owned())
+            .collect(),
+        "Polly" => POLLY_VOICES
+            .iter()
+            .map(|(id, ..)| (*id).to_owned())
+            .collect(),
+        "gCloud" => GCLOUD_VOICES
+            .iter()
+            .map(|(language_code, variant, _)| format!("{language_code} {variant}"))
+            .collect(),
+        _ => custom_voices(mode, config)?.to_vec(),
+    };
+
+    Some(codes)
+}
+
+pub(crate) fn translation_languages() -> Value {
+    TRANSLATION_LANGUAGES
+        .iter()
+        .map(|&(code, name)| json!([code, name]))
+        .collect()
+}
+
+fn custom_voices<'a>(mode: &str, config: &'a MockConfig) -> Option<&'a [String]> {
+    let mut custom_modes = config.custom_modes.iter();
+    custom_modes
+        .find(|(name, _)| name == mode)
+        .map(|(_, voices)| voices.as_slice())
+}
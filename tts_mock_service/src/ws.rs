@@ -0,0 +1,171 @@
+use std::sync::atomic::Ordering::SeqCst;
+
+use futures_util::{SinkExt as _, StreamExt as _};
+use tokio::{
+    io::{AsyncWriteExt as _, BufReader},
+    net::TcpStream,
+};
+use tokio_tungstenite::{
+    WebSocketStream,
+    tungstenite::{Message, handshake::derive_accept_key, protocol::Role},
+};
+
+use crate::{RecordedFrame, State, http::Request, voices};
+
+type WSStream = WebSocketStream<BufReader<TcpStream>>;
+
+const PROTOCOL_VERSION: u16 = 1;
+
+/// The subprotocol agreed with the bot, which decides the encoding of the hello reply.
+#[derive(Clone, Copy)]
+enum Encoding {
+    Json,
+    MessagePack,
+}
+
+impl Encoding {
+    const JSON_PROTOCOL: &str = "tts-service.json";
+    const MESSAGEPACK_PROTOCOL: &str = "tts-service.msgpack";
+
+    fn negotiate(request: &Request, state: &State) -> Option<Self> {
+        // Older tts-services ignore the offered subprotocols, so the bot retries without them.
+        if !state.config.handshake {
+            return None;
+        }
+
+        let offered: Vec<&str> = request
+            .header("sec-websocket-protocol")
+            .map(|protocols| protocols.split(',').map(str::trim).collect())
+            .unwrap_or_default();
+
+        if state.config.msgpack && offered.contains(&Self::MESSAGEPACK_PROTOCOL) {
+            Some(Self::MessagePack)
+        } else if offered.contains(&Self::JSON_PROTOCOL) {
+            Some(Self::Json)
+        } else {
+            None
+        }
+    }
+
+    fn protocol(self) -> &'static str {
+        match self {
+            Self::Json => Self::JSON_PROTOCOL,
+            Self::MessagePack => Self::MESSAGEPACK_PROTOCOL,
+        }
+    }
+
+    fn encode(self, value: &serde_json::Value) -> Message {
+        match self {
+            Self::Json => Message::Text(value.to_string().into()),
+            Self::MessagePack => Message::Binary(rmp_serde::to_vec_named(value).unwrap().into()),
+        }
+    }
+}
+
+/// Decodes a text or binary message, or [`None`] for control messages.
+fn decode(msg: &Message) -> Option<anyhow::Result<serde_json::Value>> {
+    match msg {
+        Message::Text(text) => Some(serde_json::from_str(text.as_str()).map_err(Into::into)),
+        Message::Binary(bytes) => Some(rmp_serde::from_slice(bytes).map_err(Into::into)),
+        _ => None,
+    }
+}
+
+pub(crate) async fn handle(
+    mut stream: BufReader<TcpStream>,
+    request: &Request,
+    state: &State,
+) -> anyhow::Result<()> {
+    let key = request
+        .header("sec-websocket-key")
+        .ok_or_else(|| anyhow::anyhow!("Websocket upgrade is missing Sec-WebSocket-Key"))?;
+
+    let encoding = Encoding::negotiate(request, state);
+    let accept = derive_accept_key(key.as_bytes());
+    let protocol = encoding
+        .map(|encoding| format!("Sec-WebSocket-Protocol: {}\r\n", encoding.protocol()))
+        .unwrap_or_default();
+
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\n\
+        Upgrade: websocket\r\n\
+        Connection: Upgrade\r\n\
+        Sec-WebSocket-Accept: {accept}\r\n\
+        {protocol}\r\n"
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+
+    let mut ws = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+
+    state.connections.fetch_add(1, SeqCst);
+    let res = run(&mut ws, encoding, state).await;
+    state.connections.fetch_sub(1, SeqCst);
+
+    res
+}
+
+async fn run(ws: &mut WSStream, encoding: Option<Encoding>, state: &State) -> anyhow::Result<()> {
+    if let Some(encoding) = encoding {
+        reply_to_hello(ws, encoding, state).await?;
+    }
+
+    let mut disconnect = state.disconnect.subscribe();
+    loop {
+        let msg = tokio::select! {
+            _ = disconnect.changed() => {
+                _ = ws.close(None).await;
+                return Ok(());
+            }
+            msg = ws.next() => msg,
+        };
+
+        // Pings are answered by tungstenite while reading.
+        let msg = match msg {
+            Some(Ok(Message::Close(_))) | None => return Ok(()),
+            Some(Ok(msg)) => msg,
+            Some(Err(err)) => return Err(err.into()),
+        };
+
+        if let Some(value) = decode(&msg) {
+            let value = value?;
+            let frame = RecordedFrame::from_value(&value)
+                .ok_or_else(|| anyhow::anyhow!("Received malformed frame: {value}"))?;
+
+            state.record(frame);
+        }
+    }
+}
+
+async fn reply_to_hello(
+    ws: &mut WSStream,
+    encoding: Encoding,
+    state: &State,
+) -> anyhow::Result<()> {
+    let hello = loop {
+        let msg = ws
+            .next()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Closed before hello"))??;
+        if let Some(hello) = decode(&msg) {
+            break hello?;
+        }
+    };
+
+    anyhow::ensure!(
+        hello.get("protocol_version").is_some(),
+        "Malformed hello: {hello}"
+    );
+
+    let capabilities = serde_json::json!({
+        "protocol_version": PROTOCOL_VERSION,
+        "modes": voices::modes(&state.config),
+        "formats": ["wav"],
+        "skip": state.config.skip,
+        "pause": state.config.pause,
+    });
+
+    ws.send(encoding.encode(&capabilities)).await?;
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use tts_core::{
+    catalogues::VoiceCatalogues,
+    structs::{Data, Result, TTSMode},
+};
+
+/// Logs the difference between two catalogues, returning the removed entries.
+fn diff_catalogue(name: &str, old: &BTreeSet<String>, new: &BTreeSet<String>) -> Vec<String> {
+    let added: Vec<_> = new.difference(old).collect();
+    let removed: Vec<_> = old.difference(new).cloned().collect();
+
+    if !added.is_empty() || !removed.is_empty() {
+        tracing::warn!("{name} changed, added: {added:?}, removed: {removed:?}");
+    }
+
+    removed
+}
+
+/// Refetches the voices and translation languages, so changes appear without a restart.
+pub struct CatalogueRefresher {
+    data: Arc<Data>,
+}
+
+impl CatalogueRefresher {
+    #[must_use]
+    pub fn new(data: Arc<Data>) -> Self {
+        Self { data }
+    }
+
+    /// Resets the user and guild voices that were set to a voice that no longer exists.
+    async fn reset_removed_voices(&self, mode: TTSMode, removed: &[String]) -> Result<()> {
+        let user_ids: Vec<i64> = sqlx::query_scalar(
+            "UPDATE user_voice SET voice = NULL
+            WHERE mode = $1 AND voice = ANY($2)
+            RETURNING user_id",
+        )
+        .bind(mode)
+        .bind(removed)
+        .fetch_all(&self.data.pool)
+        .await?;
+
+        // `guild_voice.voice` cannot be null, so the whole row has to go.
+        let guild_ids: Vec<i64> = sqlx::query_scalar(
+            "DELETE FROM guild_voice
+            WHERE mode = $1 AND voice = ANY($2)
+            RETURNING guild_id",
+        )
+        .bind(mode)
+        .bind(removed)
+        .fetch_all(&self.data.pool)
+        .await?;
+
+        for user_id in &user_ids {
+            self.data.user_voice_db.invalidate_cache(&(*user_id, mode));
+        }
+
+        for guild_id in &guild_ids {
+            self.data
+                .guild_voice_db
+                .invalidate_cache(&(*guild_id, mode));
+        }
+
+        tracing::warn!(
+            "Reset {} user and {} guild {mode} voices that no longer exist",
+            user_ids.len(),
+            guild_ids.len()
+        );
+
+        Ok(())
+    }
+
+    async fn reset_removed_languages(&self, removed: &[String]) -> Result<()> {
+        let guild_ids: Vec<i64> = sqlx::query_scalar(
+            "UPDATE guilds SET target_lang = NULL
+            WHERE target_lang = ANY($1)
+            RETURNING guild_id",
+        )
+        .bind(removed)
+        .fetch_all(&self.data.pool)
+        .await?;
+
+        for guild_id in &guild_ids {
+            self.data.guilds_db.invalidate_cache(guild_id);
+        }
+
+        tracing::warn!(
+            "Reset {} guild translation languages that no longer exist",
+            guild_ids.len()
+        );
+
+        Ok(())
+    }
+}
+
+impl crate::Looper for CatalogueRefresher {
+    const NAME: &'static str = "Catalogue Refresher";
+    const MILLIS: u64 = 1000 * 60 * 60;
+
+    type Error = anyhow::Error;
+    async fn loop_func(&self) -> Result<()> {
+        let tts_service = self.data.any_tts_service();
+        let mut new = VoiceCatalogues::fetch(&self.data.reqwest, &tts_service).await?;
+
+        let old = self.data.catalogues();
+        new.fill_empty_from(&old);
+
+        let mut removed_voices = Vec::new();
+        for mode in TTSMode::all() {
+            let name = format!("{mode} voices");
+            let removed = diff_catalogue(&name, &old.voice_codes(mode), &new.voice_codes(mode));
+            if !removed.is_empty() {
+                removed_voices.push((mode, removed));
+            }
+        }
+
+        let removed_languages = diff_catalogue(
+            "Translation languages",
+            &old.translation_codes(),
+            &new.translation_codes(),
+        );
+
+        // Swap before resetting, so the removed voices cannot be set again in between.
+        let new = Arc::new(new);
+        *self.data.catalogues.write() = Arc::clone(&new);
+
+        for (mode, removed) in removed_voices {
+            self.reset_removed_voices(mode, &removed).await?;
+        }
+
+        if !removed_languages.is_empty() {
+            self.reset_removed_languages(&removed_languages).await?;
+        }
+
+        new.save_snapshot(&self.data.pool).await
+    }
+}
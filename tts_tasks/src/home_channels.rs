@@ -0,0 +1,83 @@
+use serenity::all as serenity;
+
+use tts_core::{
+    structs::{Data, Result},
+    voice,
+};
+
+#[derive(sqlx::FromRow)]
+struct HomeChannelRow {
+    guild_id: i64,
+    home_channel: i64,
+}
+
+/// Keeps the bot connected to the 24/7 home channels of premium guilds on a single shard.
+pub struct HomeChannelSupervisor {
+    ctx: serenity::Context,
+}
+
+impl HomeChannelSupervisor {
+    #[must_use]
+    pub fn new(ctx: serenity::Context) -> Self {
+        Self { ctx }
+    }
+
+    async fn rejoin(&self, guild_id: serenity::GuildId, channel_id: serenity::ChannelId) {
+        let data = self.ctx.data_ref::<Data>();
+        if data
+            .is_premium_simple(&self.ctx.http, guild_id)
+            .await
+            .unwrap_or(false)
+        {
+            voice::rejoin(&self.ctx, guild_id, channel_id, "home channel");
+        }
+    }
+}
+
+impl crate::Looper for HomeChannelSupervisor {
+    const NAME: &'static str = "Home Channel Supervisor";
+    const MILLIS: u64 = 1000 * 60;
+
+    type Error = anyhow::Error;
+    async fn loop_func(&self) -> Result<()> {
+        let data = self.ctx.data_ref::<Data>();
+        let shard_count = self.ctx.cache.shard_count();
+
+        let rows: Vec<HomeChannelRow> = sqlx::query_as(
+            "SELECT guild_id, home_channel FROM guilds
+            WHERE home_channel IS NOT NULL AND premium_user IS NOT NULL
+            AND (guild_id >> 22) % $1 = $2",
+        )
+        .bind(i64::from(shard_count.get()))
+        .bind(i64::from(self.ctx.shard_id.0))
+        .fetch_all(&data.pool)
+        .await?;
+
+        let mut interval = tokio::time::interval(voice::REJOIN_INTERVAL);
+        for row in rows {
+            let guild_id = serenity::GuildId::new(row.guild_id as u64);
+            let channel_id = serenity::ChannelId::new(row.home_channel as u64);
+
+            if data.voice_connections.lock().contains_key(&guild_id)
+                || data.left_home_channels.lock().contains(&guild_id)
+            {
+                continue;
+            }
+
+            let channel_exists = self
+                .ctx
+                .cache
+                .guild(guild_id)
+                .is_some_and(|guild| guild.channels.contains_key(&channel_id));
+
+            if !channel_exists {
+                continue;
+            }
+
+            interval.tick().await;
+            self.rejoin(guild_id, channel_id).await;
+        }
+
+        Ok(())
+    }
+}
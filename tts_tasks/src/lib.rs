@@ -3,6 +3,8 @@
 
 mod analytics;
 pub mod bot_list_updater;
+pub mod catalogue_refresher;
+pub mod home_channels;
 pub mod logging;
 pub mod web_updater;
 
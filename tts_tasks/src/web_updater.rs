@@ -9,16 +9,6 @@ use serenity::all as serenity;
 
 use tts_core::structs::{Result, TTSMode, WebsiteInfo};
 
-#[allow(dead_code, clippy::match_same_arms)]
-fn remember_to_update_analytics_query() {
-    match TTSMode::gTTS {
-        TTSMode::gTTS => (),
-        TTSMode::Polly => (),
-        TTSMode::eSpeak => (),
-        TTSMode::gCloud => (),
-    }
-}
-
 fn count_members<'a>(guilds: impl Iterator<Item = serenity::cache::GuildRef<'a>>) -> u64 {
     guilds.map(|g| u64::from(g.member_count.get())).sum()
 }
@@ -74,17 +64,17 @@ impl crate::Looper for Updater {
 
         let (message_count, premium_guild_ids) = {
             let mut db_conn = self.pool.acquire().await?;
+            let tts_events: Vec<_> = TTSMode::all()
+                .map(|mode| mode.analytics_event().into_owned())
+                .collect();
+
             let message_count = sqlx::query_as::<_, AnalyticsQueryResult>(
                 "
                 SELECT count FROM analytics
-                WHERE date_collected = (CURRENT_DATE - 1) AND (
-                    event = 'gTTS_tts'   OR
-                    event = 'eSpeak_tts' OR
-                    event = 'gCloud_tts' OR
-                    event = 'Polly_tts'
-                )
+                WHERE date_collected = (CURRENT_DATE - 1) AND event = ANY($1)
             ",
             )
+            .bind(&tts_events)
             .fetch_all(&mut *db_conn)
             .await?
             .into_iter()